@@ -0,0 +1,21 @@
+use super::*;
+
+mod progress;
+
+// Re-exports
+pub(crate) use progress::*;
+
+pub(crate) struct AchievementsPlugin;
+
+impl Plugin for AchievementsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_saved::<AchievementProgress>()
+            .add_event::<AchievementUnlocked>()
+            .add_systems(
+                Update,
+                check_achievements
+                    .after(record_run_stats)
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
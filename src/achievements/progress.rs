@@ -0,0 +1,85 @@
+use super::*;
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+/// Unlocked achievements with their unlock timestamps
+#[derive(Resource, Serialize, Deserialize, Default)]
+pub(crate) struct AchievementProgress {
+    pub(crate) unlocked: HashMap<String, u64>,
+}
+
+impl Saved for AchievementProgress {
+    const KEY: &'static str = "achievements";
+}
+
+#[derive(Event)]
+pub(crate) struct AchievementUnlocked {
+    pub(crate) key: String,
+}
+
+impl AchievementGoal {
+    /// Whether the goal is met by the lifetime totals together with the run in progress
+    fn is_met(
+        &self,
+        stats: &PlayerStats,
+        score: u32,
+        flaps: u32,
+        survived: f32,
+        crashed_at: Option<u32>,
+    ) -> bool {
+        match *self {
+            AchievementGoal::Score(points) => score >= points,
+            AchievementGoal::TotalRuns(runs) => stats.total_runs >= runs,
+            AchievementGoal::TotalFlaps(total) => stats.total_flaps + flaps as u64 >= total,
+            AchievementGoal::SurviveSeconds(seconds) => survived >= seconds,
+            AchievementGoal::CrashBeforeFirstPipe => crashed_at == Some(0),
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn check_achievements(
+    mut run_ended_events: EventReader<RunEnded>,
+    mut unlocked_events: EventWriter<AchievementUnlocked>,
+    mut progress: ResMut<AchievementProgress>,
+    stats: Res<PlayerStats>,
+    list: Res<AchievementList>,
+    score: Res<Score>,
+    flaps: Res<Flaps>,
+    run_clock: Res<RunClock>,
+    game_mode: Res<GameMode>,
+) {
+    // Editor playtests can start anywhere along a level, so they do not count
+    if matches!(*game_mode, GameMode::Playtest { .. }) {
+        run_ended_events.clear();
+        return;
+    }
+
+    // The lifetime totals only count a run once it ends, by which point it is no longer in progress
+    let mut run_flaps = flaps.0;
+    let mut crashed_at = None;
+    for run_ended in run_ended_events.read() {
+        run_flaps = 0;
+        if run_ended.cause.is_some() {
+            crashed_at = Some(run_ended.pipes);
+        }
+    }
+
+    for achievement in list.achievements.iter() {
+        if progress.unlocked.contains_key(&achievement.key) {
+            continue;
+        }
+        if !achievement
+            .goal
+            .is_met(&stats, score.0, run_flaps, run_clock.0, crashed_at)
+        {
+            continue;
+        }
+        progress
+            .unlocked
+            .insert(achievement.key.clone(), unix_timestamp());
+        unlocked_events.send(AchievementUnlocked {
+            key: achievement.key.clone(),
+        });
+    }
+}
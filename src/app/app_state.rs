@@ -5,6 +5,13 @@ pub(crate) enum AppState {
     #[default]
     Loading,
     MainMenu,
+    LevelSelect,
+    Lobby,
+    Editor,
+    Birds,
+    Achievements,
+    Stats,
+    Settings,
     Playing,
     RetryMenu,
 }
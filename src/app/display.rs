@@ -0,0 +1,202 @@
+use super::*;
+use bevy::render::camera::{ScalingMode, Viewport};
+use bevy::window::PrimaryWindow;
+use serde::{Deserialize, Serialize};
+
+/// The part of the world the camera frames, which is as wide as the square playfield but may be taller
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Playfield {
+    pub(crate) height: f32,
+}
+
+impl Default for Playfield {
+    fn default() -> Playfield {
+        Playfield::SQUARE
+    }
+}
+
+impl Playfield {
+    pub(crate) const SQUARE: Playfield = Playfield {
+        height: WINDOW_HEIGHT_PX,
+    };
+
+    pub(crate) fn size(&self) -> Vec2 {
+        Vec2::new(WINDOW_WIDTH_PX, self.height)
+    }
+
+    pub(crate) fn min_y(&self) -> f32 {
+        -self.height / 2.0
+    }
+
+    pub(crate) fn max_y(&self) -> f32 {
+        self.height / 2.0
+    }
+
+    /// How many times taller than the square playfield this one is
+    pub(crate) fn stretch(&self) -> f32 {
+        self.height / WINDOW_HEIGHT_PX
+    }
+}
+
+/// The playfield that suits the shape of the window, which menus follow straight away and endless runs take on when
+/// they start
+#[derive(Resource, Default)]
+pub(crate) struct WindowPlayfield(pub(crate) Playfield);
+
+/// The camera showing the playfield, which every menu is laid out in as well
+#[derive(Component)]
+pub(crate) struct MainCamera;
+
+/// The sky drawn behind the playfield, colored by the palette
+#[derive(Component)]
+pub(crate) struct PlayfieldBackground;
+
+pub(crate) fn spawn_camera(mut commands: Commands) {
+    commands
+        .spawn((
+            Camera2d,
+            MainCamera,
+            OrthographicProjection {
+                scaling_mode: ScalingMode::Fixed {
+                    width: WINDOW_WIDTH_PX,
+                    height: WINDOW_HEIGHT_PX,
+                },
+                ..OrthographicProjection::default_2d()
+            },
+        ))
+        .with_children(|parent| {
+            // The clear color fills the letterbox bars, so the sky is drawn behind the playfield instead
+            parent.spawn((
+                Sprite::from_color(
+                    Color::srgb_from_array(STANDARD_PALETTE.background),
+                    Playfield::SQUARE.size(),
+                ),
+                Transform::from_xyz(0.0, 0.0, PLAYFIELD_BACKGROUND_Z),
+                PlayfieldBackground,
+            ));
+        });
+}
+
+/// Picks the playfield for the shape of the window, which only menus take on straight away
+pub(crate) fn fit_playfield_to_window(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    settings: Res<Settings>,
+    app_state: Res<State<AppState>>,
+    mut window_playfield: ResMut<WindowPlayfield>,
+    mut playfield: ResMut<Playfield>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let window_size = window.physical_size();
+    // Minimized windows have no shape to follow
+    if window_size.min_element() == 0 {
+        return;
+    }
+
+    let aspect_ratio = window_size.y as f32 / window_size.x as f32;
+    let fitted = if settings.portrait && aspect_ratio >= PORTRAIT_MIN_ASPECT_RATIO {
+        Playfield {
+            height: WINDOW_HEIGHT_PX * aspect_ratio.min(PORTRAIT_MAX_ASPECT_RATIO),
+        }
+    } else {
+        Playfield::SQUARE
+    };
+    if window_playfield.0 != fitted {
+        window_playfield.0 = fitted;
+    }
+
+    // A run keeps the playfield it started with until it is left, and levels are edited on the square one
+    let framed = match app_state.get() {
+        AppState::Playing | AppState::RetryMenu => return,
+        AppState::Editor => Playfield::SQUARE,
+        _ => fitted,
+    };
+    if *playfield != framed {
+        *playfield = framed;
+    }
+}
+
+/// Frames a run as it starts, where only endless runs use the portrait playfield since levels and races are laid out
+/// on the square one
+pub(crate) fn frame_run_playfield(
+    game_mode: Res<GameMode>,
+    race_session: Option<Res<RaceSession>>,
+    playback: Option<Res<ReplayPlayback>>,
+    window_playfield: Res<WindowPlayfield>,
+    mut playfield: ResMut<Playfield>,
+) {
+    let framed = match (&*game_mode, race_session, playback) {
+        (GameMode::Endless, None, Some(playback)) => playback.replay.playfield,
+        (GameMode::Endless, None, None) => window_playfield.0,
+        _ => Playfield::SQUARE,
+    };
+    if *playfield != framed {
+        *playfield = framed;
+    }
+}
+
+/// Scales the playfield to the largest size that fits the window and centers it between bars
+pub(crate) fn fit_camera_to_window(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut camera_query: Query<(&mut Camera, &mut OrthographicProjection), With<MainCamera>>,
+    mut background_query: Query<&mut Sprite, With<PlayfieldBackground>>,
+    playfield: Res<Playfield>,
+    settings: Res<Settings>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    let (Ok(window), Ok((mut camera, mut projection))) =
+        (window_query.get_single(), camera_query.get_single_mut())
+    else {
+        return;
+    };
+    let window_size = window.physical_size();
+    // Minimized windows have no size to fit into
+    if window_size.min_element() == 0 {
+        return;
+    }
+
+    let playfield_size = playfield.size();
+    if let Ok(mut background) = background_query.get_single_mut() {
+        if background.custom_size != Some(playfield_size) {
+            background.custom_size = Some(playfield_size);
+        }
+    }
+    let scaling_mode = ScalingMode::Fixed {
+        width: playfield_size.x,
+        height: playfield_size.y,
+    };
+    let projection_unchanged = matches!(
+        projection.scaling_mode,
+        ScalingMode::Fixed { width, height } if width == playfield_size.x && height == playfield_size.y
+    );
+    if !projection_unchanged {
+        projection.scaling_mode = scaling_mode;
+    }
+
+    let mut scale = (window_size.as_vec2() / playfield_size).min_element();
+    // Windows smaller than the playfield fall back to a fractional scale rather than cropping
+    if settings.scaling == PlayfieldScaling::Integer && scale >= 1.0 {
+        scale = scale.floor();
+    }
+    let physical_size = (playfield_size * scale)
+        .round()
+        .as_uvec2()
+        .clamp(UVec2::ONE, window_size);
+    let physical_position = (window_size - physical_size) / 2;
+
+    let unchanged = camera.viewport.as_ref().is_some_and(|viewport| {
+        viewport.physical_position == physical_position && viewport.physical_size == physical_size
+    });
+    if !unchanged {
+        camera.viewport = Some(Viewport {
+            physical_position,
+            physical_size,
+            ..Default::default()
+        });
+    }
+    let ui_scale_factor = scale / window.scale_factor();
+    if ui_scale.0 != ui_scale_factor {
+        ui_scale.0 = ui_scale_factor;
+    }
+}
@@ -0,0 +1,224 @@
+use super::*;
+use bevy::asset::LoadedFolder;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Settings chosen when the game is started, from the command line or a config file
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct LaunchOptions {
+    /// Seed for every run instead of a random one
+    pub seed: Option<u64>,
+    /// Mode to start in instead of the main menu
+    pub mode: Option<LaunchMode>,
+    /// `Some(true)` for borderless fullscreen, `Some(false)` for a window
+    pub fullscreen: Option<bool>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub mute: bool,
+    /// Leaves out the frames per second printed every second
+    pub no_fps_log: bool,
+    pub autoplay: bool,
+    /// Replay file to fly, which also picks the seed and mode
+    pub replay: Option<PathBuf>,
+}
+
+/// Where the game starts when it is launched
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub enum LaunchMode {
+    #[default]
+    Endless,
+    /// A level in `assets/levels/`, by file name with or without the `.level.ron` extension
+    Level(String),
+    Editor,
+}
+
+#[derive(Debug, Error)]
+pub enum LaunchError {
+    #[error("could not read {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("could not parse {path}: {source}")]
+    Ron {
+        path: String,
+        source: ron::error::SpannedError,
+    },
+    #[error("{0}")]
+    Invalid(String),
+}
+
+/// The launch mode still to be started, cleared once it has been
+#[derive(Resource, Default)]
+pub(crate) struct PendingLaunch(pub(crate) Option<LaunchMode>);
+
+impl FromStr for LaunchMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<LaunchMode, String> {
+        match value.split_once(':') {
+            None if value == "endless" => Ok(LaunchMode::Endless),
+            None if value == "editor" => Ok(LaunchMode::Editor),
+            Some(("level", name)) if !name.is_empty() => Ok(LaunchMode::Level(name.to_string())),
+            _ => Err(format!(
+                "unknown mode '{value}', expected endless, editor or level:<name>"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for LaunchMode {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LaunchMode::Endless => write!(formatter, "endless"),
+            LaunchMode::Level(name) => write!(formatter, "level:{name}"),
+            LaunchMode::Editor => write!(formatter, "editor"),
+        }
+    }
+}
+
+impl LaunchMode {
+    fn matches_level(&self, key: &str) -> bool {
+        match self {
+            LaunchMode::Level(name) => {
+                key == name || key.strip_suffix(LEVEL_FILE_EXTENSION) == Some(name)
+            }
+            _ => false,
+        }
+    }
+
+    /// Looks for the level in `assets/levels/` so a misspelled name fails at launch
+    #[cfg(not(target_arch = "wasm32"))]
+    fn check_level_exists(&self) -> Result<(), LaunchError> {
+        use bevy::asset::io::file::FileAssetReader;
+
+        let LaunchMode::Level(name) = self else {
+            return Ok(());
+        };
+        let folder = FileAssetReader::get_base_path()
+            .join(ASSETS_FOLDER)
+            .join(LEVELS_FOLDER);
+        let entries = std::fs::read_dir(&folder).map_err(|source| LaunchError::Io {
+            path: folder.display().to_string(),
+            source,
+        })?;
+        let found = entries
+            .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+            .any(|key| self.matches_level(&key));
+        if !found {
+            return Err(LaunchError::Invalid(format!(
+                "there is no level named '{name}' in {}",
+                folder.display()
+            )));
+        }
+        Ok(())
+    }
+
+    /// The browser build has no folder to look in, so the level is checked once the list loads
+    #[cfg(target_arch = "wasm32")]
+    fn check_level_exists(&self) -> Result<(), LaunchError> {
+        Ok(())
+    }
+}
+
+impl LaunchOptions {
+    pub fn from_file(path: &Path) -> Result<LaunchOptions, LaunchError> {
+        let contents = std::fs::read_to_string(path).map_err(|source| LaunchError::Io {
+            path: path.display().to_string(),
+            source,
+        })?;
+        ron::from_str(&contents).map_err(|source| LaunchError::Ron {
+            path: path.display().to_string(),
+            source,
+        })
+    }
+
+    /// Checks the values a command line parser cannot, such as whether the level to start exists
+    pub fn validate(&self) -> Result<(), LaunchError> {
+        if self.width == Some(0) || self.height == Some(0) {
+            return Err(LaunchError::Invalid(
+                "the window width and height must be above zero".to_string(),
+            ));
+        }
+        if let Some(mode) = &self.mode {
+            mode.check_level_exists()?;
+        }
+        Ok(())
+    }
+
+    /// Reads the replay file and checks that its run can be flown again
+    pub(crate) fn load_replay(&self) -> Result<Option<Replay>, LaunchError> {
+        let Some(path) = &self.replay else {
+            return Ok(None);
+        };
+        let contents = std::fs::read_to_string(path).map_err(|source| LaunchError::Io {
+            path: path.display().to_string(),
+            source,
+        })?;
+        let replay: Replay = ron::from_str(&contents).map_err(|source| LaunchError::Ron {
+            path: path.display().to_string(),
+            source,
+        })?;
+        if replay.mode == LaunchMode::Editor {
+            return Err(LaunchError::Invalid(format!(
+                "{} does not replay a run",
+                path.display()
+            )));
+        }
+        let stretch = replay.playfield.stretch();
+        if !(1.0..=PORTRAIT_MAX_ASPECT_RATIO).contains(&stretch) {
+            return Err(LaunchError::Invalid(format!(
+                "{} has a playfield height of {}, which the game never uses",
+                path.display(),
+                replay.playfield.height
+            )));
+        }
+        replay.mode.check_level_exists()?;
+        Ok(Some(replay))
+    }
+}
+
+/// Leaves the main menu for the mode given at launch, waiting for the level list if it needs a level
+pub(crate) fn start_launch_mode(
+    mut pending_launch: ResMut<PendingLaunch>,
+    level_assets: Res<LevelAssets>,
+    folders: Res<Assets<LoadedFolder>>,
+    asset_server: Res<AssetServer>,
+    mut game_mode: ResMut<GameMode>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    let Some(mode) = &pending_launch.0 else {
+        return;
+    };
+    match mode {
+        LaunchMode::Endless => {
+            *game_mode = GameMode::Endless;
+            next_app_state.set(AppState::Playing);
+        }
+        LaunchMode::Editor => next_app_state.set(AppState::Editor),
+        LaunchMode::Level(name) => {
+            let Some(folder) = folders.get(&level_assets.folder) else {
+                if asset_server.load_state(&level_assets.folder).is_failed() {
+                    error!("Could not start level {name}, the level list failed to load");
+                    pending_launch.0 = None;
+                }
+                return;
+            };
+            match folder_levels(folder)
+                .into_iter()
+                .find(|level| mode.matches_level(&level.key))
+            {
+                Some(level) => {
+                    *game_mode = GameMode::Level(level);
+                    next_app_state.set(AppState::Playing);
+                }
+                None => error!("Could not start level {name}, there is no such level"),
+            }
+        }
+    }
+    pending_launch.0 = None;
+}
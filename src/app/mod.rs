@@ -2,30 +2,79 @@ use super::*;
 use bevy::asset::AssetMetaCheck;
 
 mod app_state;
+mod display;
+mod launch;
 
 // Re-exports
 pub(crate) use app_state::*;
+pub(crate) use display::*;
+pub(crate) use launch::*;
+pub use launch::{LaunchError, LaunchMode, LaunchOptions};
 
 // Window
 pub(crate) const WINDOW_WIDTH_PX: f32 = 512.0;
 pub(crate) const WINDOW_HEIGHT_PX: f32 = 512.0;
 pub(crate) const WINDOW_MIN_X: f32 = -WINDOW_WIDTH_PX / 2.0;
+pub(crate) const WINDOW_MAX_X: f32 = WINDOW_WIDTH_PX / 2.0;
 pub(crate) const WINDOW_MAX_Y: f32 = WINDOW_HEIGHT_PX / 2.0;
-pub(crate) const WINDOW_MIN_Y: f32 = -WINDOW_HEIGHT_PX / 2.0;
 const WINDOW_NAME: &str = "Flappy Bird";
-const CANVAS_ID: &str = "#app";
+pub(crate) const CANVAS_ID: &str = "#app";
+const LETTERBOX_COLOR: [f32; 3] = [0.0, 0.0, 0.0]; // #000000
+/// Depth of the sky behind the playfield relative to the camera, below every sprite
+const PLAYFIELD_BACKGROUND_Z: f32 = -1.0;
+/// Windows at least this much taller than wide switch to the portrait playfield
+const PORTRAIT_MIN_ASPECT_RATIO: f32 = 1.2;
+/// Tallest the portrait playfield grows relative to its width, with bars beyond it on even taller windows
+const PORTRAIT_MAX_ASPECT_RATIO: f32 = 1.5;
 
-pub struct AppPlugin;
+// Launch
+const LEVEL_FILE_EXTENSION: &str = ".level.ron";
+
+pub struct AppPlugin {
+    options: LaunchOptions,
+    replay: Option<Replay>,
+}
+
+impl AppPlugin {
+    /// Reads any replay the launch options name before the app is built
+    pub fn new(options: LaunchOptions) -> Result<AppPlugin, LaunchError> {
+        let replay = options.load_replay()?;
+        Ok(AppPlugin { options, replay })
+    }
+}
 
 impl Plugin for AppPlugin {
     fn build(&self, app: &mut App) {
+        let options = &self.options;
+        let resolution = Vec2::new(
+            options.width.map_or(WINDOW_WIDTH_PX, |width| width as f32),
+            options
+                .height
+                .map_or(WINDOW_HEIGHT_PX, |height| height as f32),
+        );
+        // The window opens the way it was last left unless the command line says otherwise
+        let settings = load_saved::<Settings>();
+        let fullscreen = options.fullscreen.unwrap_or(settings.fullscreen);
+        // A replay decides the seed and mode so that the run plays out the same way again
+        let (seed, mode) = match &self.replay {
+            Some(replay) => (Some(replay.seed), Some(replay.mode.clone())),
+            None => (options.seed, options.mode.clone()),
+        };
+        if let Some(replay) = &self.replay {
+            app.insert_resource(ReplayPlayback::new(replay.clone()));
+        }
+
         app.add_plugins(
             DefaultPlugins
                 .set(WindowPlugin {
                     primary_window: Some(Window {
                         title: WINDOW_NAME.to_string(),
                         canvas: Some(CANVAS_ID.into()),
-                        resolution: Vec2::new(WINDOW_WIDTH_PX, WINDOW_HEIGHT_PX).into(),
+                        resolution: resolution.into(),
+                        // Lets the page size the canvas so phones get the whole screen
+                        fit_canvas_to_parent: true,
+                        mode: window_mode(fullscreen),
+                        present_mode: present_mode(settings.vsync),
                         ..Default::default()
                     }),
                     ..Default::default()
@@ -36,6 +85,21 @@ impl Plugin for AppPlugin {
                     ..Default::default()
                 }),
         )
+        .insert_resource(ClearColor(Color::srgb_from_array(LETTERBOX_COLOR)))
+        .init_resource::<Playfield>()
+        .init_resource::<WindowPlayfield>()
+        .add_systems(
+            Update,
+            (fit_playfield_to_window, fit_camera_to_window).chain(),
+        )
+        .insert_resource(FixedSeed(seed))
+        .insert_resource(PendingLaunch(mode))
+        .insert_resource(Autoplay(options.autoplay))
+        .insert_resource(Muted(options.mute))
+        .add_systems(
+            Update,
+            start_launch_mode.run_if(in_state(AppState::MainMenu)),
+        )
         .init_state::<AppState>()
         .add_systems(
             PostStartup,
@@ -56,21 +120,95 @@ impl Plugin for AppPlugin {
                 entered: AppState::Playing,
             },
             (
-                despawn_main_menu,
-                spawn_score_display,
-                spawn_player,
-                spawn_pipes,
-                |mut next_player_state: ResMut<NextState<PlayerState>>| {
-                    next_player_state.set(PlayerState::WaitingToStart);
-                },
-            ),
+                (roll_run_seed, frame_run_playfield),
+                (
+                    despawn_main_menu,
+                    spawn_score_display,
+                    spawn_coin_display,
+                    spawn_player,
+                    spawn_pipes,
+                    |mut next_player_state: ResMut<NextState<PlayerState>>| {
+                        next_player_state.set(PlayerState::WaitingToStart);
+                    },
+                ),
+            )
+                .chain(),
+        )
+        .add_systems(
+            OnTransition {
+                exited: AppState::MainMenu,
+                entered: AppState::LevelSelect,
+            },
+            (despawn_main_menu, spawn_level_select),
+        )
+        .add_systems(
+            OnTransition {
+                exited: AppState::LevelSelect,
+                entered: AppState::MainMenu,
+            },
+            (despawn_level_select, spawn_main_menu),
+        )
+        .add_systems(
+            OnTransition {
+                exited: AppState::LevelSelect,
+                entered: AppState::Playing,
+            },
+            (
+                frame_run_playfield,
+                (
+                    despawn_level_select,
+                    spawn_score_display,
+                    spawn_coin_display,
+                    spawn_player,
+                    spawn_pipes,
+                    |mut next_player_state: ResMut<NextState<PlayerState>>| {
+                        next_player_state.set(PlayerState::WaitingToStart);
+                    },
+                ),
+            )
+                .chain(),
+        )
+        .add_systems(
+            OnTransition {
+                exited: AppState::MainMenu,
+                entered: AppState::Lobby,
+            },
+            (despawn_main_menu, spawn_lobby_menu),
+        )
+        .add_systems(
+            OnTransition {
+                exited: AppState::Lobby,
+                entered: AppState::MainMenu,
+            },
+            (despawn_lobby_menu, leave_race_session, spawn_main_menu),
+        )
+        .add_systems(
+            OnTransition {
+                exited: AppState::Lobby,
+                entered: AppState::Playing,
+            },
+            (
+                frame_run_playfield,
+                (
+                    despawn_lobby_menu,
+                    reset_race_tracking,
+                    spawn_score_display,
+                    spawn_coin_display,
+                    spawn_player,
+                    spawn_pipes,
+                    |mut next_player_state: ResMut<NextState<PlayerState>>| {
+                        next_player_state.set(PlayerState::WaitingToStart);
+                    },
+                ),
+            )
+                .chain(),
         )
         .add_systems(
             OnTransition {
                 exited: AppState::Playing,
                 entered: AppState::RetryMenu,
             },
-            spawn_retry_menu,
+            (record_level_result, deposit_run_coins, spawn_retry_menu).chain(),
         )
         .add_systems(
             OnTransition {
@@ -81,10 +219,18 @@ impl Plugin for AppPlugin {
                 (
                     despawn_retry_menu,
                     despawn_score_display,
+                    despawn_coin_display,
                     despawn_player,
                     despawn_pipes,
+                    roll_run_seed,
+                    frame_run_playfield,
+                ),
+                (
+                    spawn_score_display,
+                    spawn_coin_display,
+                    spawn_player,
+                    spawn_pipes,
                 ),
-                (spawn_score_display, spawn_player, spawn_pipes),
                 |mut next_player_state: ResMut<NextState<PlayerState>>| {
                     next_player_state.set(PlayerState::WaitingToStart);
                 },
@@ -100,17 +246,166 @@ impl Plugin for AppPlugin {
                 (
                     despawn_retry_menu,
                     despawn_score_display,
+                    despawn_coin_display,
                     despawn_player,
                     despawn_pipes,
+                    despawn_ghosts,
+                    leave_race_session,
                 ),
                 spawn_main_menu,
             )
                 .chain(),
         )
-        .add_plugins((GameplayPlugin, GameAssetsPlugin, GameUiPlugin));
-    }
-}
+        .add_systems(
+            OnTransition {
+                exited: AppState::RetryMenu,
+                entered: AppState::Lobby,
+            },
+            (
+                (
+                    despawn_retry_menu,
+                    despawn_score_display,
+                    despawn_coin_display,
+                    despawn_player,
+                    despawn_pipes,
+                    despawn_ghosts,
+                ),
+                spawn_lobby_menu,
+            )
+                .chain(),
+        )
+        .add_systems(
+            OnTransition {
+                exited: AppState::MainMenu,
+                entered: AppState::Editor,
+            },
+            (despawn_main_menu, open_editor_course, spawn_editor_toolbar).chain(),
+        )
+        .add_systems(
+            OnTransition {
+                exited: AppState::Editor,
+                entered: AppState::MainMenu,
+            },
+            (
+                despawn_editor_toolbar,
+                despawn_editor_sprites,
+                reset_editor_camera,
+                close_editor_course,
+                spawn_main_menu,
+            ),
+        )
+        .add_systems(
+            OnTransition {
+                exited: AppState::Editor,
+                entered: AppState::Playing,
+            },
+            (
+                (
+                    despawn_editor_toolbar,
+                    despawn_editor_sprites,
+                    reset_editor_camera,
+                    frame_run_playfield,
+                ),
+                (
+                    spawn_score_display,
+                    spawn_coin_display,
+                    spawn_player,
+                    spawn_pipes,
+                ),
+                |mut next_player_state: ResMut<NextState<PlayerState>>| {
+                    next_player_state.set(PlayerState::WaitingToStart);
+                },
+            )
+                .chain(),
+        )
+        .add_systems(
+            OnTransition {
+                exited: AppState::RetryMenu,
+                entered: AppState::Editor,
+            },
+            (
+                (
+                    despawn_retry_menu,
+                    despawn_score_display,
+                    despawn_coin_display,
+                    despawn_player,
+                    despawn_pipes,
+                ),
+                spawn_editor_toolbar,
+            )
+                .chain(),
+        )
+        .add_systems(
+            OnTransition {
+                exited: AppState::MainMenu,
+                entered: AppState::Birds,
+            },
+            (despawn_main_menu, spawn_bird_shop),
+        )
+        .add_systems(
+            OnTransition {
+                exited: AppState::Birds,
+                entered: AppState::MainMenu,
+            },
+            (despawn_bird_shop, spawn_main_menu),
+        )
+        .add_systems(
+            OnTransition {
+                exited: AppState::MainMenu,
+                entered: AppState::Achievements,
+            },
+            (despawn_main_menu, spawn_achievements_menu),
+        )
+        .add_systems(
+            OnTransition {
+                exited: AppState::Achievements,
+                entered: AppState::MainMenu,
+            },
+            (despawn_achievements_menu, spawn_main_menu),
+        )
+        .add_systems(
+            OnTransition {
+                exited: AppState::MainMenu,
+                entered: AppState::Settings,
+            },
+            (despawn_main_menu, spawn_settings_menu),
+        )
+        .add_systems(
+            OnTransition {
+                exited: AppState::Settings,
+                entered: AppState::MainMenu,
+            },
+            (despawn_settings_menu, spawn_main_menu),
+        )
+        .add_systems(
+            OnTransition {
+                exited: AppState::MainMenu,
+                entered: AppState::Stats,
+            },
+            (despawn_main_menu, spawn_stats_menu),
+        )
+        .add_systems(
+            OnTransition {
+                exited: AppState::Stats,
+                entered: AppState::MainMenu,
+            },
+            (despawn_stats_menu, spawn_main_menu),
+        )
+        .add_plugins((
+            GameplayPlugin,
+            GameAssetsPlugin,
+            AudioMixerPlugin,
+            CameraEffectsPlugin,
+            ParticlesPlugin,
+            GameUiPlugin,
+            RacePlugin,
+            EditorPlugin,
+            AchievementsPlugin,
+            StatsPlugin,
+            SettingsPlugin,
+        ));
 
-fn spawn_camera(mut commands: Commands) {
-    commands.spawn(Camera2d::default());
+        // A launch override applies to this session only, so it is kept apart from the saved settings
+        app.insert_resource(FullscreenOverride(options.fullscreen));
+    }
 }
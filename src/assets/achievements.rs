@@ -0,0 +1,118 @@
+use super::*;
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::utils::HashSet;
+use serde::Deserialize;
+use thiserror::Error;
+
+/// Every achievement that can be unlocked, loaded from `assets/achievements.ron`
+#[derive(Asset, Resource, TypePath, Deserialize, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct AchievementList {
+    pub(crate) achievements: Vec<Achievement>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Achievement {
+    /// Also names the achievement's text in `assets/locales/`
+    pub(crate) key: String,
+    pub(crate) goal: AchievementGoal,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub(crate) enum AchievementGoal {
+    Score(u32),
+    TotalRuns(u32),
+    TotalFlaps(u64),
+    SurviveSeconds(f32),
+    CrashBeforeFirstPipe,
+}
+
+#[derive(Resource)]
+pub(crate) struct AchievementAssets {
+    pub(crate) list: Handle<AchievementList>,
+}
+
+#[derive(Default)]
+pub(crate) struct AchievementListLoader;
+
+#[derive(Debug, Error)]
+pub(crate) enum AchievementListLoaderError {
+    #[error("could not read achievements file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse achievements file: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    #[error("invalid achievement: {0}")]
+    Invalid(String),
+}
+
+impl AchievementList {
+    fn validate(&self) -> Result<(), String> {
+        let mut keys = HashSet::new();
+        for achievement in self.achievements.iter() {
+            if !keys.insert(achievement.key.as_str()) {
+                return Err(format!("the key {} is used twice", achievement.key));
+            }
+            if let AchievementGoal::SurviveSeconds(seconds) = achievement.goal {
+                if !(seconds.is_finite() && seconds > 0.0) {
+                    return Err(format!(
+                        "{} needs a survival time above 0, got {seconds}",
+                        achievement.key
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// Shares the plain "ron" extension with the tuning file, typed loads pick the right loader
+impl AssetLoader for AchievementListLoader {
+    type Asset = AchievementList;
+    type Settings = ();
+    type Error = AchievementListLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<AchievementList, AchievementListLoaderError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let list = ron::de::from_bytes::<AchievementList>(&bytes)?;
+        list.validate()
+            .map_err(AchievementListLoaderError::Invalid)?;
+        Ok(list)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ron"]
+    }
+}
+
+pub(crate) fn insert_achievement_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(AchievementAssets {
+        list: asset_server.load(ACHIEVEMENTS),
+    });
+}
+
+pub(crate) fn apply_achievement_list(
+    mut commands: Commands,
+    mut asset_events: EventReader<AssetEvent<AchievementList>>,
+    achievement_assets: Res<AchievementAssets>,
+    lists: Res<Assets<AchievementList>>,
+) {
+    for event in asset_events.read() {
+        let updated = match event {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => {
+                *id == achievement_assets.list.id()
+            }
+            _ => false,
+        };
+        if let Some(list) = lists.get(&achievement_assets.list).filter(|_| updated) {
+            commands.insert_resource(list.clone());
+        }
+    }
+}
@@ -3,12 +3,20 @@ use bevy::audio::Volume;
 
 #[derive(Resource)]
 pub(crate) struct AudioAssets {
-    pub(crate) fall_sound: Handle<AudioSource>,
-    pub(crate) flap_sound: Handle<AudioSource>,
-    pub(crate) score_sound: Handle<AudioSource>,
-    pub(crate) smack_sound: Handle<AudioSource>,
-    pub(crate) swoosh_sound: Handle<AudioSource>,
-    pub(crate) music: Handle<AudioSource>,
+    // Sound effects hold every variant of the effect
+    pub(crate) fall_sounds: Vec<Handle<AudioSource>>,
+    pub(crate) flap_sounds: Vec<Handle<AudioSource>>,
+    pub(crate) score_sounds: Vec<Handle<AudioSource>>,
+    pub(crate) smack_sounds: Vec<Handle<AudioSource>>,
+    pub(crate) swoosh_sounds: Vec<Handle<AudioSource>>,
+    pub(crate) power_up_sounds: Vec<Handle<AudioSource>>,
+    pub(crate) coin_sounds: Vec<Handle<AudioSource>>,
+    pub(crate) click_sounds: Vec<Handle<AudioSource>>,
+    pub(crate) menu_music: Handle<AudioSource>,
+    pub(crate) gameplay_music: Handle<AudioSource>,
+    /// Plays in step with the gameplay music and is faded in as the score rises
+    pub(crate) gameplay_intensity_music: Handle<AudioSource>,
+    pub(crate) defeat_sting: Handle<AudioSource>,
 }
 
 pub(crate) fn insert_audio_assets(
@@ -18,11 +26,21 @@ pub(crate) fn insert_audio_assets(
 ) {
     volume.volume = Volume::new(GLOBAL_SOUND_VOLUME);
     commands.insert_resource(AudioAssets {
-        fall_sound: asset_server.load(FALL_SOUND),
-        flap_sound: asset_server.load(FLAP_SOUND),
-        score_sound: asset_server.load(SCORE_SOUND),
-        smack_sound: asset_server.load(SMACK_SOUND),
-        swoosh_sound: asset_server.load(SWOOSH_SOUND),
-        music: asset_server.load(MUSIC),
+        fall_sounds: load_variants(&asset_server, FALL_SOUNDS),
+        flap_sounds: load_variants(&asset_server, FLAP_SOUNDS),
+        score_sounds: load_variants(&asset_server, SCORE_SOUNDS),
+        smack_sounds: load_variants(&asset_server, SMACK_SOUNDS),
+        swoosh_sounds: load_variants(&asset_server, SWOOSH_SOUNDS),
+        power_up_sounds: load_variants(&asset_server, POWER_UP_SOUNDS),
+        coin_sounds: load_variants(&asset_server, COIN_SOUNDS),
+        click_sounds: load_variants(&asset_server, CLICK_SOUNDS),
+        menu_music: asset_server.load(MENU_MUSIC),
+        gameplay_music: asset_server.load(GAMEPLAY_MUSIC),
+        gameplay_intensity_music: asset_server.load(GAMEPLAY_INTENSITY_MUSIC),
+        defeat_sting: asset_server.load(DEFEAT_STING),
     });
 }
+
+fn load_variants(asset_server: &AssetServer, paths: &[&'static str]) -> Vec<Handle<AudioSource>> {
+    paths.iter().map(|path| asset_server.load(*path)).collect()
+}
@@ -4,11 +4,29 @@ use super::*;
 pub(crate) struct FontAssets {
     pub(crate) button_font: Handle<Font>,
     pub(crate) title_font: Handle<Font>,
+    pub(crate) fallback_font: Handle<Font>,
 }
 
 pub(crate) fn insert_font_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.insert_resource(FontAssets {
         button_font: asset_server.load(BUTTON_FONT),
         title_font: asset_server.load(TITLE_FONT),
+        fallback_font: asset_server.load(FALLBACK_FONT),
     });
 }
+
+impl FontAssets {
+    /// How much to scale `text` by when it has to be drawn with the fallback font instead of
+    /// `font`, or `None` when `font` has every letter it needs
+    pub(crate) fn fallback_scale(&self, font: &Handle<Font>, text: &str) -> Option<f32> {
+        let (characters, scale) = if *font == self.button_font {
+            (BUTTON_FONT_CHARACTERS, BUTTON_FALLBACK_FONT_SCALE)
+        } else if *font == self.title_font {
+            (TITLE_FONT_CHARACTERS, TITLE_FALLBACK_FONT_SCALE)
+        } else {
+            return None;
+        };
+        let covered = |letter: char| characters.iter().any(|range| range.contains(&letter));
+        (!text.chars().all(covered)).then_some(scale)
+    }
+}
@@ -0,0 +1,155 @@
+use super::*;
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext, LoadedFolder};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// A fixed course of pipe pairs, loaded from a `.level.ron` file in `assets/levels/`.
+///
+/// Distances and heights use the same sprite pixel units as [`GameTuning`].
+#[derive(Asset, TypePath, Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Level {
+    pub(crate) name: String,
+    /// Flaps allowed for a three star finish
+    pub(crate) par_flaps: u32,
+    pub(crate) pipes: Vec<LevelPipe>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct LevelPipe {
+    /// Horizontal distance from the previous pipe pair, or from the right edge for the first
+    pub(crate) distance: f32,
+    /// Height of the gap's center above the middle of the screen
+    pub(crate) gap_y: f32,
+    /// Half the height of the gap
+    pub(crate) gap_size: f32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) motion: Option<PipeMotion>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub(crate) enum PipeMotion {
+    /// Moves the gap up and down by `amplitude` every `period` seconds
+    Oscillate { amplitude: f32, period: f32 },
+    /// Closes each pipe in towards the gap by `closing` and opens it again every `period` seconds
+    Jaws { closing: f32, period: f32 },
+    /// Starts with the gap moved by `offset` and slides it into place over the first `distance`
+    /// after the pipes come on screen
+    SlideIn { offset: f32, distance: f32 },
+}
+
+#[derive(Resource)]
+pub(crate) struct LevelAssets {
+    pub(crate) folder: Handle<LoadedFolder>,
+}
+
+#[derive(Default)]
+pub(crate) struct LevelLoader;
+
+#[derive(Debug, Error)]
+pub(crate) enum LevelLoaderError {
+    #[error("could not read level file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse level file: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    #[error("invalid level: {0}")]
+    Invalid(String),
+}
+
+impl Level {
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        if self.pipes.is_empty() {
+            return Err("a level needs at least one pipe".to_string());
+        }
+        for (index, pipe) in self.pipes.iter().enumerate() {
+            if !(pipe.distance.is_finite() && pipe.distance >= PIPE_WIDTH_PX) {
+                return Err(format!(
+                    "pipe {index} distance must be at least the pipe width of {PIPE_WIDTH_PX}, got {}",
+                    pipe.distance
+                ));
+            }
+            if !(pipe.gap_size.is_finite() && pipe.gap_size > 0.0) {
+                return Err(format!(
+                    "pipe {index} gap_size must be greater than 0, got {}",
+                    pipe.gap_size
+                ));
+            }
+            if !pipe.gap_y.is_finite()
+                || pipe.gap_y.abs() + pipe.gap_size >= WINDOW_MAX_Y / SPRITE_SCALE
+            {
+                return Err(format!(
+                    "pipe {index} gap must stay on screen, got gap_y {} with gap_size {}",
+                    pipe.gap_y, pipe.gap_size
+                ));
+            }
+            if let Some(motion) = pipe.motion {
+                motion
+                    .validate(pipe.gap_size)
+                    .map_err(|error| format!("pipe {index} {error}"))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl PipeMotion {
+    /// Checks the motion against the half height of the gap it is applied to
+    pub(crate) fn validate(&self, gap_size: f32) -> Result<(), String> {
+        match *self {
+            PipeMotion::Oscillate { amplitude, period } => {
+                if !(amplitude.is_finite() && period.is_finite() && period > 0.0) {
+                    return Err(
+                        "oscillation needs a finite amplitude and a period above 0".to_string()
+                    );
+                }
+            }
+            PipeMotion::Jaws { closing, period } => {
+                if !(period.is_finite() && period > 0.0) {
+                    return Err(format!("jaws need a period above 0, got {period}"));
+                }
+                if !(closing.is_finite() && closing >= 0.0 && closing < gap_size) {
+                    return Err(format!(
+                        "jaws closing must be from 0 up to the gap_size of {gap_size}, got {closing}"
+                    ));
+                }
+            }
+            PipeMotion::SlideIn { offset, distance } => {
+                if !(offset.is_finite() && distance.is_finite() && distance > 0.0) {
+                    return Err("slide in needs a finite offset and a distance above 0".to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl AssetLoader for LevelLoader {
+    type Asset = Level;
+    type Settings = ();
+    type Error = LevelLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Level, LevelLoaderError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let level = ron::de::from_bytes::<Level>(&bytes)?;
+        level.validate().map_err(LevelLoaderError::Invalid)?;
+        Ok(level)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["level.ron"]
+    }
+}
+
+pub(crate) fn insert_level_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(LevelAssets {
+        folder: asset_server.load_folder(LEVELS_FOLDER),
+    });
+}
@@ -0,0 +1,297 @@
+use super::*;
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::utils::HashMap;
+use serde::Deserialize;
+use thiserror::Error;
+
+/// A language the interface can be shown in, from [`LANGUAGES`]
+pub(crate) struct Language {
+    /// Saved in the settings to remember the choice
+    pub(crate) key: &'static str,
+    pub(crate) path: &'static str,
+}
+
+/// The interface's text in one language, loaded from `assets/locales/`
+#[derive(Asset, Resource, TypePath, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Locale {
+    /// The language's own name for itself
+    pub(crate) name: String,
+    strings: HashMap<TextKey, String>,
+    /// Achievement names and descriptions by [`Achievement::key`]
+    achievements: HashMap<String, AchievementText>,
+    /// Bird names by [`Skin::key`]
+    skins: HashMap<String, String>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+struct AchievementText {
+    name: String,
+    description: String,
+}
+
+/// Declares [`TextKey`] together with the list of all its keys, so the two can't drift apart
+macro_rules! text_keys {
+    ($($key:ident),* $(,)?) => {
+        /// Every piece of interface text that changes with the language
+        #[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub(crate) enum TextKey {
+            $($key),*
+        }
+
+        impl TextKey {
+            const ALL: &'static [TextKey] = &[$(TextKey::$key),*];
+        }
+    };
+}
+
+text_keys! {
+    Title,
+    Play,
+    Levels,
+    Race,
+    Editor,
+    Birds,
+    Awards,
+    Stats,
+    Settings,
+    Back,
+    Playtest,
+    Save,
+    SkinEquipped,
+    SkinEquip,
+    SkinBuy,
+    AchievementLocked,
+    StatsRuns,
+    StatsFlaps,
+    StatsPipes,
+    StatsPlayTime,
+    StatsAverage,
+    StatsMedian,
+    StatsLongest,
+    StatsDeaths,
+    StatsTopPipe,
+    StatsBottomPipe,
+    StatsCeiling,
+    StatsFloor,
+    StatsChart,
+    StatsNoRuns,
+    ScalingSetting,
+    ScalingFit,
+    ScalingInteger,
+    PortraitSetting,
+    FullscreenSetting,
+    VSyncSetting,
+    FrameCapSetting,
+    FilteringSetting,
+    FilteringSharp,
+    FilteringSmooth,
+    MasterVolumeSetting,
+    MusicVolumeSetting,
+    SfxVolumeSetting,
+    UiVolumeSetting,
+    ScreenEffectsSetting,
+    ShakeSetting,
+    FlashSetting,
+    HitStopSetting,
+    LanguageSetting,
+    HighContrastSetting,
+    ReducedMotionSetting,
+    GameSpeedSetting,
+    ForgivingSetting,
+    SettingOn,
+    SettingOff,
+    DisplayPage,
+    SoundPage,
+    EffectsPage,
+    LanguagePage,
+    AccessPage,
+    NoLevels,
+    LobbyIdle,
+    LobbySearching,
+    LobbyWaiting,
+    LobbyRacers,
+    Host,
+    Join,
+    Start,
+    HostLeft,
+    HostLost,
+    PortInUse,
+    Unavailable,
+    LevelCleared,
+    LevelBest,
+    Retry,
+    MainMenu,
+    Edit,
+    ToastAchievement,
+    ToastExported,
+    ToastExportFailed,
+    ToastSeeLog,
+    EditorNoPipes,
+    EditorInvalidPipe,
+    EditorSaveFailed,
+    EditorNativeOnly,
+}
+
+/// Handles to every locale, in the same order as [`LANGUAGES`]
+#[derive(Resource)]
+pub(crate) struct LocaleAssets {
+    locales: Vec<Handle<Locale>>,
+}
+
+#[derive(Default)]
+pub(crate) struct LocaleLoader;
+
+#[derive(Debug, Error)]
+pub(crate) enum LocaleLoaderError {
+    #[error("could not read locale file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse locale file: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    #[error("invalid locale: {0}")]
+    Invalid(String),
+}
+
+// Used until the chosen language loads, and kept if it never does
+impl Default for Locale {
+    fn default() -> Locale {
+        ron::de::from_str(BUILT_IN_LOCALE).expect("the built-in locale is valid")
+    }
+}
+
+impl Locale {
+    /// The text for `key`, which every locale has once [`Locale::fill_missing`] has run
+    pub(crate) fn text(&self, key: TextKey) -> &str {
+        self.strings.get(&key).map_or("", String::as_str)
+    }
+
+    /// The name of the achievement saved as `key`, or the key itself if no language names it
+    pub(crate) fn achievement_name<'a>(&'a self, key: &'a str) -> &'a str {
+        self.achievements
+            .get(key)
+            .map_or(key, |achievement| achievement.name.as_str())
+    }
+
+    pub(crate) fn achievement_description(&self, key: &str) -> &str {
+        self.achievements
+            .get(key)
+            .map_or("", |achievement| achievement.description.as_str())
+    }
+
+    /// The name of the skin saved as `key`, or the key itself if no language names it
+    pub(crate) fn skin_name<'a>(&'a self, key: &'a str) -> &'a str {
+        self.skins.get(key).map_or(key, String::as_str)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.name.is_empty() {
+            return Err("the language needs a name".to_string());
+        }
+        Ok(())
+    }
+
+    /// Fills text this locale lacks from `fallback`, returning the keys that were missing
+    fn fill_missing(&mut self, fallback: &Locale) -> Vec<String> {
+        let mut missing = Vec::new();
+        for key in TextKey::ALL {
+            if !self.strings.contains_key(key) {
+                self.strings.insert(*key, fallback.text(*key).to_string());
+                missing.push(format!("{key:?}"));
+            }
+        }
+        for (key, text) in fallback.achievements.iter() {
+            if !self.achievements.contains_key(key) {
+                self.achievements.insert(key.clone(), text.clone());
+                missing.push(key.clone());
+            }
+        }
+        for (key, name) in fallback.skins.iter() {
+            if !self.skins.contains_key(key) {
+                self.skins.insert(key.clone(), name.clone());
+                missing.push(key.clone());
+            }
+        }
+        missing
+    }
+}
+
+impl LocaleAssets {
+    /// The locale saved as `language`, or the first one if it is no longer around
+    fn get(&self, language: &str) -> Option<&Handle<Locale>> {
+        let index = LANGUAGES
+            .iter()
+            .position(|candidate| candidate.key == language)
+            .unwrap_or_default();
+        self.locales.get(index)
+    }
+}
+
+// Shares the plain "ron" extension with the tuning file, typed loads pick the right loader
+impl AssetLoader for LocaleLoader {
+    type Asset = Locale;
+    type Settings = ();
+    type Error = LocaleLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Locale, LocaleLoaderError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let mut locale = ron::de::from_bytes::<Locale>(&bytes)?;
+        locale.validate().map_err(LocaleLoaderError::Invalid)?;
+        // A half translated language shows the rest in English rather than blank labels
+        let missing = locale.fill_missing(&Locale::default());
+        if !missing.is_empty() {
+            warn!(
+                "{} has no text for {missing:?}, showing English instead",
+                load_context.path().display()
+            );
+        }
+        Ok(locale)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ron"]
+    }
+}
+
+pub(crate) fn insert_locale_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(LocaleAssets {
+        locales: LANGUAGES
+            .iter()
+            .map(|language| asset_server.load(language.path))
+            .collect(),
+    });
+}
+
+/// Switches to the chosen language once it has loaded, and again whenever its file changes
+pub(crate) fn apply_locale(
+    mut commands: Commands,
+    mut asset_events: EventReader<AssetEvent<Locale>>,
+    locale_assets: Res<LocaleAssets>,
+    locales: Res<Assets<Locale>>,
+    settings: Res<Settings>,
+    mut applied: Local<Option<AssetId<Locale>>>,
+) {
+    let Some(handle) = locale_assets.get(&settings.language) else {
+        return;
+    };
+    let mut updated = *applied != Some(handle.id());
+    for event in asset_events.read() {
+        if let AssetEvent::Modified { id } = event {
+            updated |= *id == handle.id();
+        }
+    }
+    if !updated {
+        return;
+    }
+    if let Some(locale) = locales.get(handle) {
+        commands.insert_resource(locale.clone());
+        *applied = Some(handle.id());
+    }
+}
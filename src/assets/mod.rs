@@ -1,35 +1,166 @@
 use super::*;
+use std::ops::RangeInclusive;
 
+mod achievements;
 mod audio;
 mod fonts;
+mod levels;
+mod locales;
+mod particles;
+mod skins;
 mod sprites;
+mod tuning;
 
 // Re-exports
+pub(crate) use achievements::*;
 pub(crate) use audio::*;
 pub(crate) use fonts::*;
+pub(crate) use levels::*;
+pub(crate) use locales::*;
+pub(crate) use particles::*;
+pub(crate) use skins::*;
 pub(crate) use sprites::*;
+pub(crate) use tuning::*;
+
+// Tuning
+const TUNING: &str = "tuning.ron";
+
+// Achievements
+const ACHIEVEMENTS: &str = "achievements.ron";
+
+// Particles
+const PARTICLES: &str = "particles.ron";
+/// Particles allocated up front, which bursts draw from and never grow past
+pub(crate) const PARTICLE_POOL_SIZE: usize = 128;
+
+// Levels
+/// Folder the desktop build reads assets from, relative to the working directory
+pub(crate) const ASSETS_FOLDER: &str = "assets";
+pub(crate) const LEVELS_FOLDER: &str = "levels";
+
+// Locales
+pub(crate) const DEFAULT_LANGUAGE: &str = "en";
+pub(crate) const LANGUAGES: [Language; 4] = [
+    Language {
+        key: DEFAULT_LANGUAGE,
+        path: "locales/en.ron",
+    },
+    Language {
+        key: "es",
+        path: "locales/es.ron",
+    },
+    Language {
+        key: "de",
+        path: "locales/de.ron",
+    },
+    Language {
+        key: "ru",
+        path: "locales/ru.ron",
+    },
+];
+/// English built into the game, shown until a language loads and for any text a language lacks
+const BUILT_IN_LOCALE: &str = include_str!("../../assets/locales/en.ron");
 
 // Fonts
 const BUTTON_FONT: &str = "fonts/mini_pixel-7.ttf";
 const TITLE_FONT: &str = "fonts/light_pixel-7.ttf";
+/// Draws text with letters the pixel fonts are missing
+const FALLBACK_FONT: &str = "fonts/DejaVuSans.ttf";
+/// Letters the button font has, beyond which text switches to the fallback font
+const BUTTON_FONT_CHARACTERS: &[RangeInclusive<char>] = &[
+    ' '..='~',
+    'Ё'..='я',
+    'ё'..='џ',
+    'Ґ'..='ґ',
+    '–'..='—',
+    '‘'..='‚',
+    '“'..='„',
+    '…'..='…',
+    '€'..='€',
+    '№'..='№',
+];
+const TITLE_FONT_CHARACTERS: &[RangeInclusive<char>] = &[
+    ' '..='~',
+    '\u{a0}'..='®',
+    '°'..='ÿ',
+    'Œ'..='œ',
+    'Š'..='š',
+    'Ÿ'..='Ÿ',
+    'Ž'..='ž',
+    'Ё'..='я',
+    'ё'..='џ',
+    'Ґ'..='ґ',
+    '–'..='—',
+    '‘'..='‚',
+    '“'..='„',
+    '…'..='…',
+    '€'..='€',
+    '№'..='№',
+];
+/// The fallback font is drawn larger than the pixel fonts at the same size, so it is scaled to match
+const BUTTON_FALLBACK_FONT_SCALE: f32 = 0.6;
+const TITLE_FALLBACK_FONT_SCALE: f32 = 1.0;
 
 // Sprites
 pub(crate) const SPRITE_SCALE: f32 = 4.0;
 const PIPE_SPRITE: &str = "sprites/pipe.png";
 const PLAYER_SPRITE: &str = "sprites/bird.png";
+const SHIELD_SPRITE: &str = "sprites/power_ups/shield.png";
+const SLOW_MOTION_SPRITE: &str = "sprites/power_ups/slow_motion.png";
+const SHRINK_SPRITE: &str = "sprites/power_ups/shrink.png";
+const DOUBLE_SCORE_SPRITE: &str = "sprites/power_ups/double_score.png";
+const COIN_SPRITE: &str = "sprites/coin.png";
+
+// Skins
+pub(crate) const DEFAULT_SKIN: &str = "classic";
+pub(crate) const SKINS: [Skin; 4] = [
+    Skin {
+        key: DEFAULT_SKIN,
+        price: 0,
+        bird_sprite: PLAYER_SPRITE,
+        pipe_sprite: PIPE_SPRITE,
+    },
+    Skin {
+        key: "cardinal",
+        price: 50,
+        bird_sprite: "sprites/skins/cardinal/bird.png",
+        pipe_sprite: "sprites/skins/cardinal/pipe.png",
+    },
+    Skin {
+        key: "bluebird",
+        price: 100,
+        bird_sprite: "sprites/skins/bluebird/bird.png",
+        pipe_sprite: "sprites/skins/bluebird/pipe.png",
+    },
+    Skin {
+        key: "night_owl",
+        price: 200,
+        bird_sprite: "sprites/skins/night_owl/bird.png",
+        pipe_sprite: "sprites/skins/night_owl/pipe.png",
+    },
+];
 
 pub(crate) const PLAYER_SPRITE_Z: f32 = 1.0;
+pub(crate) const GHOST_SPRITE_Z: f32 = 0.5;
+pub(crate) const PICKUP_SPRITE_Z: f32 = 0.25;
+/// Behind the bird so feathers and debris never hide it
+pub(crate) const PARTICLE_SPRITE_Z: f32 = 0.75;
 pub(crate) const PIPE_SPRITE_Z: f32 = 0.0;
 
-const GAME_BACKGROUND_COLOR: [f32; 3] = [0.565, 0.855, 1.0]; // #90daff
-
 // Audio
-const FALL_SOUND: &str = "sounds/fall.ogg";
-const FLAP_SOUND: &str = "sounds/flap.ogg";
-const SCORE_SOUND: &str = "sounds/score.ogg";
-const SMACK_SOUND: &str = "sounds/smack.ogg";
-const SWOOSH_SOUND: &str = "sounds/swoosh.ogg";
-const MUSIC: &str = "sounds/music.ogg";
+// Each effect picks one of its variants at random every time it plays
+const FALL_SOUNDS: &[&str] = &["sounds/fall.ogg"];
+const FLAP_SOUNDS: &[&str] = &["sounds/flap.ogg", "sounds/flap_2.ogg", "sounds/flap_3.ogg"];
+const SCORE_SOUNDS: &[&str] = &["sounds/score.ogg"];
+const SMACK_SOUNDS: &[&str] = &["sounds/smack.ogg"];
+const SWOOSH_SOUNDS: &[&str] = &["sounds/swoosh.ogg"];
+const POWER_UP_SOUNDS: &[&str] = &["sounds/power_up.ogg"];
+const COIN_SOUNDS: &[&str] = &["sounds/coin.ogg", "sounds/coin_2.ogg"];
+const CLICK_SOUNDS: &[&str] = &["sounds/click.ogg"];
+const MENU_MUSIC: &str = "sounds/music.ogg";
+const GAMEPLAY_MUSIC: &str = "sounds/gameplay_music.ogg";
+const GAMEPLAY_INTENSITY_MUSIC: &str = "sounds/gameplay_intensity.ogg";
+const DEFEAT_STING: &str = "sounds/defeat_sting.ogg";
 
 const GLOBAL_SOUND_VOLUME: f32 = 0.3;
 pub(crate) const FALL_SOUND_VOLUME: f32 = 1.0;
@@ -37,19 +168,53 @@ pub(crate) const FLAP_SOUND_VOLUME: f32 = 1.0;
 pub(crate) const SCORE_SOUND_VOLUME: f32 = 1.0;
 pub(crate) const SMACK_SOUND_VOLUME: f32 = 1.0;
 pub(crate) const SWOOSH_SOUND_VOLUME: f32 = 1.0;
-pub(crate) const MUSIC_VOLUME: f32 = 1.0;
+pub(crate) const POWER_UP_SOUND_VOLUME: f32 = 0.8;
+pub(crate) const COIN_SOUND_VOLUME: f32 = 0.6;
+pub(crate) const CLICK_SOUND_VOLUME: f32 = 0.7;
+pub(crate) const MENU_MUSIC_VOLUME: f32 = 1.0;
+pub(crate) const GAMEPLAY_MUSIC_VOLUME: f32 = 1.0;
+pub(crate) const DEFEAT_STING_VOLUME: f32 = 1.0;
 
 pub(crate) struct GameAssetsPlugin;
 
 impl Plugin for GameAssetsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Startup,
-            (
-                insert_audio_assets,
-                insert_sprite_assets,
-                insert_font_assets,
-            ),
-        );
+        app.init_asset::<GameTuning>()
+            .init_asset_loader::<GameTuningLoader>()
+            .init_resource::<GameTuning>()
+            .init_asset::<AchievementList>()
+            .init_asset_loader::<AchievementListLoader>()
+            .init_resource::<AchievementList>()
+            .init_asset::<ParticleEffects>()
+            .init_asset_loader::<ParticleEffectsLoader>()
+            .init_resource::<ParticleEffects>()
+            .init_asset::<Locale>()
+            .init_asset_loader::<LocaleLoader>()
+            .init_resource::<Locale>()
+            .init_asset::<Level>()
+            .init_asset_loader::<LevelLoader>()
+            .add_systems(
+                Startup,
+                (
+                    insert_audio_assets,
+                    insert_sprite_assets,
+                    insert_skin_assets,
+                    insert_font_assets,
+                    insert_tuning_assets,
+                    insert_level_assets,
+                    insert_achievement_assets,
+                    insert_particle_assets,
+                    insert_locale_assets,
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    apply_game_tuning,
+                    apply_achievement_list,
+                    apply_particle_effects,
+                    apply_locale,
+                ),
+            );
     }
 }
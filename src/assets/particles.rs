@@ -0,0 +1,186 @@
+use super::*;
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use serde::Deserialize;
+use thiserror::Error;
+
+/// The particle bursts the game plays, loaded from `assets/particles.ron`
+#[derive(Asset, Resource, TypePath, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ParticleEffects {
+    pub(crate) flap: ParticleEmitter,
+    pub(crate) pass: ParticleEmitter,
+    pub(crate) crash: ParticleEmitter,
+}
+
+/// One burst of particles, see `assets/particles.ron` for what each field means
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ParticleEmitter {
+    pub(crate) count: u32,
+    pub(crate) colors: Vec<[f32; 3]>,
+    pub(crate) lifetime: (f32, f32),
+    pub(crate) speed: (f32, f32),
+    pub(crate) direction: f32,
+    pub(crate) spread: f32,
+    pub(crate) gravity: f32,
+    pub(crate) drag: f32,
+    pub(crate) size: (f32, f32),
+    pub(crate) spin: f32,
+    pub(crate) offset: (f32, f32),
+    pub(crate) fade: bool,
+}
+
+#[derive(Resource)]
+pub(crate) struct ParticleAssets {
+    pub(crate) effects: Handle<ParticleEffects>,
+}
+
+#[derive(Default)]
+pub(crate) struct ParticleEffectsLoader;
+
+#[derive(Debug, Error)]
+pub(crate) enum ParticleEffectsLoaderError {
+    #[error("could not read particles file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse particles file: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    #[error("invalid particle emitter: {0}")]
+    Invalid(String),
+}
+
+// Used until the file loads, and kept if it never does
+impl Default for ParticleEffects {
+    fn default() -> ParticleEffects {
+        let emitter = ParticleEmitter {
+            count: 6,
+            colors: vec![[1.0, 1.0, 1.0]],
+            lifetime: (0.3, 0.6),
+            speed: (60.0, 120.0),
+            direction: 90.0,
+            spread: 360.0,
+            gravity: 0.0,
+            drag: 2.0,
+            size: (5.0, 1.0),
+            spin: 0.0,
+            offset: (0.0, 0.0),
+            fade: true,
+        };
+        ParticleEffects {
+            flap: emitter.clone(),
+            pass: emitter.clone(),
+            crash: emitter,
+        }
+    }
+}
+
+impl ParticleEffects {
+    fn validate(&self) -> Result<(), String> {
+        for (name, emitter) in [
+            ("flap", &self.flap),
+            ("pass", &self.pass),
+            ("crash", &self.crash),
+        ] {
+            emitter
+                .validate()
+                .map_err(|error| format!("{name}: {error}"))?;
+        }
+        Ok(())
+    }
+}
+
+impl ParticleEmitter {
+    fn validate(&self) -> Result<(), String> {
+        if self.count as usize > PARTICLE_POOL_SIZE {
+            return Err(format!(
+                "count must be at most {PARTICLE_POOL_SIZE}, got {}",
+                self.count
+            ));
+        }
+        if self.colors.is_empty() {
+            return Err("needs at least one color".to_string());
+        }
+        for (range_name, (min, max)) in [
+            ("lifetime", self.lifetime),
+            ("speed", self.speed),
+            ("size", self.size),
+        ] {
+            if !(min.is_finite() && max.is_finite() && min >= 0.0) {
+                return Err(format!("{range_name} must not be negative"));
+            }
+            if range_name != "size" && min > max {
+                return Err(format!("{range_name} minimum is above its maximum"));
+            }
+        }
+        if self.lifetime.1 <= 0.0 {
+            return Err("lifetime must be above 0".to_string());
+        }
+        for (name, value) in [
+            ("direction", self.direction),
+            ("spread", self.spread),
+            ("gravity", self.gravity),
+            ("drag", self.drag),
+            ("offset", self.offset.0),
+            ("offset", self.offset.1),
+        ] {
+            if !value.is_finite() {
+                return Err(format!("{name} must be a finite number, got {value}"));
+            }
+        }
+        // Spin is drawn from -spin to spin, which must not be an empty range
+        if !(self.spin.is_finite() && self.spin >= 0.0) {
+            return Err(format!("spin must not be negative, got {}", self.spin));
+        }
+        Ok(())
+    }
+}
+
+impl AssetLoader for ParticleEffectsLoader {
+    type Asset = ParticleEffects;
+    type Settings = ();
+    type Error = ParticleEffectsLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<ParticleEffects, ParticleEffectsLoaderError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let effects = ron::de::from_bytes::<ParticleEffects>(&bytes)?;
+        effects
+            .validate()
+            .map_err(ParticleEffectsLoaderError::Invalid)?;
+        Ok(effects)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ron"]
+    }
+}
+
+pub(crate) fn insert_particle_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(ParticleAssets {
+        effects: asset_server.load(PARTICLES),
+    });
+}
+
+pub(crate) fn apply_particle_effects(
+    mut commands: Commands,
+    mut asset_events: EventReader<AssetEvent<ParticleEffects>>,
+    particle_assets: Res<ParticleAssets>,
+    effects: Res<Assets<ParticleEffects>>,
+) {
+    for event in asset_events.read() {
+        let updated = match event {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => {
+                *id == particle_assets.effects.id()
+            }
+            _ => false,
+        };
+        if let Some(effects) = effects.get(&particle_assets.effects).filter(|_| updated) {
+            commands.insert_resource(effects.clone());
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use super::*;
+
+/// A bird and pipe look from [`SKINS`]
+pub(crate) struct Skin {
+    /// Also names the skin's text in `assets/locales/`
+    pub(crate) key: &'static str,
+    pub(crate) price: u64,
+    pub(crate) bird_sprite: &'static str,
+    pub(crate) pipe_sprite: &'static str,
+}
+
+/// Sprites for every skin, in the same order as [`SKINS`]
+#[derive(Resource)]
+pub(crate) struct SkinAssets {
+    pub(crate) bird_sprites: Vec<Handle<Image>>,
+    pub(crate) pipe_sprites: Vec<Handle<Image>>,
+}
+
+pub(crate) fn insert_skin_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(SkinAssets {
+        bird_sprites: SKINS
+            .iter()
+            .map(|skin| asset_server.load(skin.bird_sprite))
+            .collect(),
+        pipe_sprites: SKINS
+            .iter()
+            .map(|skin| asset_server.load(skin.pipe_sprite))
+            .collect(),
+    });
+}
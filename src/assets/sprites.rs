@@ -4,12 +4,32 @@ use super::*;
 pub(crate) struct SpriteAssets {
     pub(crate) pipe_sprite: Handle<Image>,
     pub(crate) player_sprite: Handle<Image>,
+    pub(crate) shield_sprite: Handle<Image>,
+    pub(crate) slow_motion_sprite: Handle<Image>,
+    pub(crate) shrink_sprite: Handle<Image>,
+    pub(crate) double_score_sprite: Handle<Image>,
+    pub(crate) coin_sprite: Handle<Image>,
+}
+
+impl SpriteAssets {
+    pub(crate) fn power_up_sprite(&self, kind: PowerUpKind) -> &Handle<Image> {
+        match kind {
+            PowerUpKind::Shield => &self.shield_sprite,
+            PowerUpKind::SlowMotion => &self.slow_motion_sprite,
+            PowerUpKind::Shrink => &self.shrink_sprite,
+            PowerUpKind::DoubleScore => &self.double_score_sprite,
+        }
+    }
 }
 
 pub(crate) fn insert_sprite_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
-    commands.insert_resource(ClearColor(Color::srgb_from_array(GAME_BACKGROUND_COLOR)));
     commands.insert_resource(SpriteAssets {
         pipe_sprite: asset_server.load(PIPE_SPRITE),
         player_sprite: asset_server.load(PLAYER_SPRITE),
+        shield_sprite: asset_server.load(SHIELD_SPRITE),
+        slow_motion_sprite: asset_server.load(SLOW_MOTION_SPRITE),
+        shrink_sprite: asset_server.load(SHRINK_SPRITE),
+        double_score_sprite: asset_server.load(DOUBLE_SCORE_SPRITE),
+        coin_sprite: asset_server.load(COIN_SPRITE),
     });
 }
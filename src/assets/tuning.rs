@@ -0,0 +1,275 @@
+use super::*;
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use serde::Deserialize;
+use thiserror::Error;
+
+/// Physics and layout numbers for a run, loaded from `assets/tuning.ron`.
+///
+/// Distances are in sprite pixels before [`SPRITE_SCALE`] unless noted, speeds and forces are in
+/// world units per second and delays are in seconds.
+#[derive(Asset, Resource, TypePath, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct GameTuning {
+    pub(crate) player_flap_force: f32,
+    pub(crate) playing_gravity_strength: f32,
+    pub(crate) animation_gravity_strength: f32,
+    pub(crate) velocity_to_rotation_ratio: f32,
+    pub(crate) player_collision_factor: f32,
+    pub(crate) pipe_amount: u32,
+    pub(crate) pipe_gap_size: f32,
+    pub(crate) pipe_vertical_offset: f32,
+    pub(crate) pipe_spacing: f32,
+    pub(crate) pipe_speed: f32,
+    pub(crate) moving_pipe_chance: f32,
+    pub(crate) moving_pipes: Vec<MovingPipeTier>,
+    pub(crate) fall_sound_delay: f32,
+    pub(crate) fall_reset_delay: f32,
+}
+
+/// A pipe motion that endless runs start mixing in once `from_pipes` pipes have been passed
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct MovingPipeTier {
+    pub(crate) from_pipes: u32,
+    pub(crate) motion: PipeMotion,
+}
+
+#[derive(Resource)]
+pub(crate) struct TuningAssets {
+    pub(crate) tuning: Handle<GameTuning>,
+}
+
+#[derive(Default)]
+pub(crate) struct GameTuningLoader;
+
+#[derive(Debug, Error)]
+pub(crate) enum GameTuningLoaderError {
+    #[error("could not read tuning file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse tuning file: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    #[error("invalid tuning value: {0}")]
+    Invalid(String),
+}
+
+impl Default for GameTuning {
+    fn default() -> GameTuning {
+        GameTuning {
+            player_flap_force: 500.0,
+            playing_gravity_strength: 1800.0,
+            animation_gravity_strength: 750.0,
+            velocity_to_rotation_ratio: 13.0,
+            player_collision_factor: 0.5,
+            pipe_amount: 4,
+            pipe_gap_size: 15.0,
+            pipe_vertical_offset: 30.0,
+            pipe_spacing: 60.0,
+            pipe_speed: 150.0,
+            moving_pipe_chance: 0.35,
+            moving_pipes: vec![
+                MovingPipeTier {
+                    from_pipes: 10,
+                    motion: PipeMotion::Oscillate {
+                        amplitude: 12.0,
+                        period: 2.5,
+                    },
+                },
+                MovingPipeTier {
+                    from_pipes: 20,
+                    motion: PipeMotion::Jaws {
+                        closing: 6.0,
+                        period: 2.0,
+                    },
+                },
+                MovingPipeTier {
+                    from_pipes: 30,
+                    motion: PipeMotion::SlideIn {
+                        offset: 80.0,
+                        distance: 60.0,
+                    },
+                },
+                MovingPipeTier {
+                    from_pipes: 30,
+                    motion: PipeMotion::SlideIn {
+                        offset: -80.0,
+                        distance: 60.0,
+                    },
+                },
+            ],
+            fall_sound_delay: 0.5,
+            fall_reset_delay: 1.75,
+        }
+    }
+}
+
+impl GameTuning {
+    pub(crate) fn pipe_spacing_scaled(&self) -> f32 {
+        self.pipe_spacing * SPRITE_SCALE
+    }
+
+    /// Seconds between two pipe pairs passing the player
+    pub(crate) fn pipe_interval(&self) -> f32 {
+        self.pipe_spacing_scaled() / self.pipe_speed
+    }
+
+    pub(crate) fn player_collision_radius(&self) -> f32 {
+        PLAYER_WIDTH_PX * SPRITE_SCALE / 2.0 * self.player_collision_factor
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let named_values = [
+            ("player_flap_force", self.player_flap_force),
+            ("playing_gravity_strength", self.playing_gravity_strength),
+            (
+                "animation_gravity_strength",
+                self.animation_gravity_strength,
+            ),
+            (
+                "velocity_to_rotation_ratio",
+                self.velocity_to_rotation_ratio,
+            ),
+            ("player_collision_factor", self.player_collision_factor),
+            ("pipe_gap_size", self.pipe_gap_size),
+            ("pipe_vertical_offset", self.pipe_vertical_offset),
+            ("pipe_spacing", self.pipe_spacing),
+            ("pipe_speed", self.pipe_speed),
+            ("moving_pipe_chance", self.moving_pipe_chance),
+            ("fall_sound_delay", self.fall_sound_delay),
+            ("fall_reset_delay", self.fall_reset_delay),
+        ];
+        for (name, value) in named_values {
+            if !value.is_finite() {
+                return Err(format!("{name} must be a finite number, got {value}"));
+            }
+        }
+
+        let positive_values = [
+            ("player_flap_force", self.player_flap_force),
+            ("playing_gravity_strength", self.playing_gravity_strength),
+            (
+                "animation_gravity_strength",
+                self.animation_gravity_strength,
+            ),
+            (
+                "velocity_to_rotation_ratio",
+                self.velocity_to_rotation_ratio,
+            ),
+            ("player_collision_factor", self.player_collision_factor),
+            ("pipe_gap_size", self.pipe_gap_size),
+            ("pipe_spacing", self.pipe_spacing),
+            ("pipe_speed", self.pipe_speed),
+        ];
+        for (name, value) in positive_values {
+            if value <= 0.0 {
+                return Err(format!("{name} must be greater than 0, got {value}"));
+            }
+        }
+
+        let non_negative_values = [
+            ("pipe_vertical_offset", self.pipe_vertical_offset),
+            ("fall_sound_delay", self.fall_sound_delay),
+            ("fall_reset_delay", self.fall_reset_delay),
+        ];
+        for (name, value) in non_negative_values {
+            if value < 0.0 {
+                return Err(format!("{name} must not be negative, got {value}"));
+            }
+        }
+
+        if self.player_collision_factor > 1.0 {
+            return Err(format!(
+                "player_collision_factor must be at most 1, got {}",
+                self.player_collision_factor
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.moving_pipe_chance) {
+            return Err(format!(
+                "moving_pipe_chance must be from 0 to 1, got {}",
+                self.moving_pipe_chance
+            ));
+        }
+        for (index, tier) in self.moving_pipes.iter().enumerate() {
+            tier.motion
+                .validate(self.pipe_gap_size)
+                .map_err(|error| format!("moving_pipes entry {index} {error}"))?;
+        }
+        if self.pipe_spacing < PIPE_WIDTH_PX {
+            return Err(format!(
+                "pipe_spacing must be at least the pipe width of {PIPE_WIDTH_PX}, got {}",
+                self.pipe_spacing
+            ));
+        }
+        let pipe_span = self.pipe_amount as f32 * self.pipe_spacing_scaled();
+        if pipe_span < WINDOW_WIDTH_PX + PIPE_WIDTH_PX * SPRITE_SCALE {
+            return Err(format!(
+                "pipe_amount * pipe_spacing must cover the window so pipes do not pop in, \
+                 got {} * {} (need at least {})",
+                self.pipe_amount,
+                self.pipe_spacing,
+                (WINDOW_WIDTH_PX / SPRITE_SCALE + PIPE_WIDTH_PX) / self.pipe_amount.max(1) as f32
+            ));
+        }
+        let gap_reach = (self.pipe_gap_size + self.pipe_vertical_offset) * SPRITE_SCALE;
+        if gap_reach >= WINDOW_MAX_Y {
+            return Err(format!(
+                "pipe_gap_size + pipe_vertical_offset must keep gaps on screen, got {} + {} \
+                 (must be below {})",
+                self.pipe_gap_size,
+                self.pipe_vertical_offset,
+                WINDOW_MAX_Y / SPRITE_SCALE
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl AssetLoader for GameTuningLoader {
+    type Asset = GameTuning;
+    type Settings = ();
+    type Error = GameTuningLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<GameTuning, GameTuningLoaderError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let tuning = ron::de::from_bytes::<GameTuning>(&bytes)?;
+        tuning.validate().map_err(GameTuningLoaderError::Invalid)?;
+        Ok(tuning)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ron"]
+    }
+}
+
+pub(crate) fn insert_tuning_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(TuningAssets {
+        tuning: asset_server.load(TUNING),
+    });
+}
+
+// Rejected files never produce an event, so the last valid tuning stays active
+pub(crate) fn apply_game_tuning(
+    mut commands: Commands,
+    mut asset_events: EventReader<AssetEvent<GameTuning>>,
+    tuning_assets: Res<TuningAssets>,
+    tunings: Res<Assets<GameTuning>>,
+) {
+    for event in asset_events.read() {
+        let updated = match event {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => {
+                *id == tuning_assets.tuning.id()
+            }
+            _ => false,
+        };
+        if let Some(tuning) = tunings.get(&tuning_assets.tuning).filter(|_| updated) {
+            info!("Applied game tuning from {TUNING}");
+            commands.insert_resource(tuning.clone());
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use super::*;
+use bevy::audio::Volume;
+
+/// Mix group a sound plays on, each with its own volume setting
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum AudioBus {
+    Music,
+    Sfx,
+    Ui,
+}
+
+/// Volume a sound was asked to play at, before its bus is mixed in
+#[derive(Component)]
+pub(crate) struct SoundVolume(pub(crate) f32);
+
+/// Turns the music down for a moment while this sound plays
+#[derive(Component)]
+pub(crate) struct DucksMusic;
+
+/// Silences every bus for this session without touching the saved volumes
+#[derive(Resource, Default)]
+pub(crate) struct Muted(pub(crate) bool);
+
+/// Counts down how long the music stays turned down after a ducking sound starts
+#[derive(Resource)]
+pub(crate) struct MusicDuck(Timer);
+
+impl Default for MusicDuck {
+    fn default() -> MusicDuck {
+        let mut timer =
+            Timer::from_seconds(DUCK_HOLD_DURATION + DUCK_RELEASE_DURATION, TimerMode::Once);
+        timer.tick(timer.duration());
+        MusicDuck(timer)
+    }
+}
+
+impl MusicDuck {
+    /// Share of the music volume let through, which holds low and then eases back up
+    fn level(&self) -> f32 {
+        let release = (self.0.elapsed_secs() - DUCK_HOLD_DURATION) / DUCK_RELEASE_DURATION;
+        DUCKED_MUSIC_VOLUME.lerp(1.0, release.clamp(0.0, 1.0))
+    }
+}
+
+impl AudioBus {
+    fn volume(self, settings: &Settings) -> f32 {
+        let bus_volume = match self {
+            AudioBus::Music => settings.music_volume,
+            AudioBus::Sfx => settings.sfx_volume,
+            AudioBus::Ui => settings.ui_volume,
+        };
+        settings.master_volume * bus_volume
+    }
+}
+
+fn mixed_volume(
+    bus: AudioBus,
+    volume: &SoundVolume,
+    fade: Option<&MusicFade>,
+    settings: &Settings,
+    muted: &Muted,
+    duck: &MusicDuck,
+) -> f32 {
+    if muted.0 {
+        return 0.0;
+    }
+    let mut mixed = volume.0 * bus.volume(settings);
+    if bus == AudioBus::Music {
+        mixed *= duck.level() * fade.map_or(1.0, |fade| fade.level);
+    }
+    mixed
+}
+
+pub(crate) fn mix_new_sound(
+    trigger: Trigger<OnAdd, SoundVolume>,
+    mut sound_query: Query<(
+        &AudioBus,
+        &SoundVolume,
+        Option<&MusicFade>,
+        &mut PlaybackSettings,
+    )>,
+    settings: Res<Settings>,
+    muted: Res<Muted>,
+    duck: Res<MusicDuck>,
+) {
+    if let Ok((bus, volume, fade, mut playback)) = sound_query.get_mut(trigger.entity()) {
+        playback.volume = Volume::new(mixed_volume(*bus, volume, fade, &settings, &muted, &duck));
+    }
+}
+
+pub(crate) fn mix_playing_sounds(
+    sound_query: Query<(&AudioBus, &SoundVolume, Option<&MusicFade>, &AudioSink)>,
+    settings: Res<Settings>,
+    muted: Res<Muted>,
+    duck: Res<MusicDuck>,
+) {
+    for (bus, volume, fade, sink) in sound_query.iter() {
+        let mixed = mixed_volume(*bus, volume, fade, &settings, &muted, &duck);
+        if sink.volume() != mixed {
+            sink.set_volume(mixed);
+        }
+    }
+}
+
+pub(crate) fn start_music_ducks(
+    duck_query: Query<(), Added<DucksMusic>>,
+    mut duck: ResMut<MusicDuck>,
+) {
+    if !duck_query.is_empty() {
+        duck.0.reset();
+    }
+}
+
+// Real time so the music recovers on schedule during slow motion
+pub(crate) fn release_music_duck(mut duck: ResMut<MusicDuck>, time: Res<Time<Real>>) {
+    if !duck.0.finished() {
+        duck.0.tick(time.delta());
+    }
+}
@@ -0,0 +1,178 @@
+use super::*;
+use bevy::audio::{PlaybackMode, Volume};
+use rand::seq::SliceRandom;
+use rand::{thread_rng, Rng};
+
+/// The game's one-shot sounds
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum SoundEffect {
+    Flap,
+    Score,
+    Smack,
+    Fall,
+    PowerUp,
+    Coin,
+    Swoosh,
+    Click,
+}
+
+/// Asks for a sound effect to be played through the effect pool
+#[derive(Event)]
+pub(crate) struct PlaySound(pub(crate) SoundEffect);
+
+/// A sound effect that is playing, along with when it started so the oldest can make way
+#[derive(Component)]
+pub(crate) struct SoundEffectInstance {
+    effect: SoundEffect,
+    started: f32,
+}
+
+/// How a sound effect is played
+struct SoundEffectConfig {
+    bus: AudioBus,
+    volume: f32,
+    /// Largest change in playback speed either way, which also shifts the pitch
+    pitch_variation: f32,
+    /// Largest share of the volume taken away at random
+    volume_variation: f32,
+    /// Most copies allowed to play at once
+    max_instances: usize,
+    ducks_music: bool,
+}
+
+impl SoundEffect {
+    fn config(self) -> SoundEffectConfig {
+        let sfx = SoundEffectConfig {
+            bus: AudioBus::Sfx,
+            volume: 1.0,
+            pitch_variation: SFX_PITCH_VARIATION,
+            volume_variation: SFX_VOLUME_VARIATION,
+            max_instances: SFX_MAX_INSTANCES,
+            ducks_music: false,
+        };
+        match self {
+            SoundEffect::Flap => SoundEffectConfig {
+                volume: FLAP_SOUND_VOLUME,
+                max_instances: FLAP_MAX_INSTANCES,
+                ..sfx
+            },
+            SoundEffect::Score => SoundEffectConfig {
+                volume: SCORE_SOUND_VOLUME,
+                ..sfx
+            },
+            SoundEffect::Smack => SoundEffectConfig {
+                volume: SMACK_SOUND_VOLUME,
+                max_instances: 1,
+                ducks_music: true,
+                ..sfx
+            },
+            SoundEffect::Fall => SoundEffectConfig {
+                volume: FALL_SOUND_VOLUME,
+                max_instances: 1,
+                ..sfx
+            },
+            SoundEffect::PowerUp => SoundEffectConfig {
+                volume: POWER_UP_SOUND_VOLUME,
+                ..sfx
+            },
+            SoundEffect::Coin => SoundEffectConfig {
+                volume: COIN_SOUND_VOLUME,
+                ..sfx
+            },
+            // Interface sounds stay exactly as recorded
+            SoundEffect::Swoosh => SoundEffectConfig {
+                bus: AudioBus::Ui,
+                volume: SWOOSH_SOUND_VOLUME,
+                pitch_variation: 0.0,
+                volume_variation: 0.0,
+                max_instances: 1,
+                ..sfx
+            },
+            SoundEffect::Click => SoundEffectConfig {
+                bus: AudioBus::Ui,
+                volume: CLICK_SOUND_VOLUME,
+                pitch_variation: 0.0,
+                volume_variation: 0.0,
+                max_instances: 2,
+                ..sfx
+            },
+        }
+    }
+
+    fn variants(self, audio: &AudioAssets) -> &[Handle<AudioSource>] {
+        match self {
+            SoundEffect::Flap => &audio.flap_sounds,
+            SoundEffect::Score => &audio.score_sounds,
+            SoundEffect::Smack => &audio.smack_sounds,
+            SoundEffect::Fall => &audio.fall_sounds,
+            SoundEffect::PowerUp => &audio.power_up_sounds,
+            SoundEffect::Coin => &audio.coin_sounds,
+            SoundEffect::Swoosh => &audio.swoosh_sounds,
+            SoundEffect::Click => &audio.click_sounds,
+        }
+    }
+}
+
+/// Plays requested effects, cutting off the oldest copy of an effect already at its limit
+///
+/// Effects despawn themselves once they finish, so the pool only holds what is audible.
+pub(crate) fn play_sound_effects(
+    mut commands: Commands,
+    mut play_sound_events: EventReader<PlaySound>,
+    instance_query: Query<(Entity, &SoundEffectInstance)>,
+    audio: Res<AudioAssets>,
+    time: Res<Time<Real>>,
+) {
+    if play_sound_events.is_empty() {
+        return;
+    }
+    let mut playing: Vec<(Entity, SoundEffect, f32)> = instance_query
+        .iter()
+        .map(|(entity, instance)| (entity, instance.effect, instance.started))
+        .collect();
+    let mut rng = thread_rng();
+    for PlaySound(effect) in play_sound_events.read() {
+        let config = effect.config();
+        let Some(source) = effect.variants(&audio).choose(&mut rng) else {
+            continue;
+        };
+
+        // Cuts off the oldest copies until there is room for one more
+        loop {
+            let copies = playing
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, playing_effect, _))| playing_effect == effect);
+            if copies.clone().count() < config.max_instances.max(1) {
+                break;
+            }
+            let Some((oldest, _)) = copies.min_by(|(_, a), (_, b)| a.2.total_cmp(&b.2)) else {
+                break;
+            };
+            let (entity, _, _) = playing.swap_remove(oldest);
+            commands.entity(entity).despawn();
+        }
+
+        let volume = config.volume * (1.0 - rng.gen_range(0.0..=config.volume_variation));
+        let speed = 1.0 + rng.gen_range(-config.pitch_variation..=config.pitch_variation);
+        let mut sound = commands.spawn((
+            AudioPlayer::new(source.clone()),
+            PlaybackSettings {
+                mode: PlaybackMode::Despawn,
+                volume: Volume::new(volume),
+                speed,
+                ..Default::default()
+            },
+            config.bus,
+            SoundVolume(volume),
+            SoundEffectInstance {
+                effect: *effect,
+                started: time.elapsed_secs(),
+            },
+        ));
+        if config.ducks_music {
+            sound.insert(DucksMusic);
+        }
+        playing.push((sound.id(), *effect, time.elapsed_secs()));
+    }
+}
@@ -0,0 +1,72 @@
+use super::*;
+
+mod buses;
+mod effects;
+mod music;
+mod soundtrack;
+
+// Re-exports
+pub(crate) use buses::*;
+pub(crate) use effects::*;
+pub(crate) use music::*;
+pub(crate) use soundtrack::*;
+
+// Buses
+/// Volumes the Settings menu steps through for each bus
+pub(crate) const BUS_VOLUME_STEPS: [f32; 5] = [0.0, 0.25, 0.5, 0.75, 1.0];
+
+// Sound effects
+/// Largest change in playback speed either way, which shifts the pitch of repeated effects
+const SFX_PITCH_VARIATION: f32 = 0.06;
+/// Largest share of the volume taken away at random
+const SFX_VOLUME_VARIATION: f32 = 0.15;
+const SFX_MAX_INSTANCES: usize = 4;
+/// Flaps can be spammed, so only the most recent few are heard
+const FLAP_MAX_INSTANCES: usize = 3;
+
+// Music
+const MUSIC_CROSSFADE_DURATION: f32 = 1.0;
+
+// Soundtrack
+/// Score at which the gameplay music starts to build up
+const INTENSITY_START_SCORE: f32 = 5.0;
+/// Score at which the gameplay music is at full intensity
+const INTENSITY_FULL_SCORE: f32 = 25.0;
+/// Seconds the intensity takes to go from nothing to full
+const INTENSITY_FADE_DURATION: f32 = 2.0;
+
+// Ducking
+/// Share of the music volume left while a loud effect plays over it
+const DUCKED_MUSIC_VOLUME: f32 = 0.35;
+const DUCK_HOLD_DURATION: f32 = 0.5;
+const DUCK_RELEASE_DURATION: f32 = 0.75;
+
+pub(crate) struct AudioMixerPlugin;
+
+impl Plugin for AudioMixerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Muted>()
+            .init_resource::<MusicDuck>()
+            .init_resource::<MusicIntensity>()
+            .add_event::<PlayMusic>()
+            .add_event::<PlaySound>()
+            .add_systems(
+                Update,
+                (
+                    play_state_music.run_if(state_changed::<AppState>),
+                    crossfade_music,
+                    fade_music,
+                    follow_score_intensity.run_if(in_state(AppState::Playing)),
+                    apply_music_intensity,
+                    play_sound_effects,
+                    start_music_ducks,
+                    release_music_duck,
+                    mix_playing_sounds,
+                )
+                    .chain(),
+            )
+            .add_systems(OnEnter(AppState::Playing), reset_music_intensity)
+            // Sounds are mixed as they are spawned so none starts at the wrong volume
+            .add_observer(mix_new_sound);
+    }
+}
@@ -0,0 +1,123 @@
+use super::*;
+use bevy::audio::{PlaybackMode, Volume};
+
+/// A piece of music for the music bus
+#[derive(Clone)]
+pub(crate) struct MusicTrack {
+    pub(crate) source: Handle<AudioSource>,
+    pub(crate) volume: f32,
+    /// Loops until replaced, otherwise plays once and is gone
+    pub(crate) looped: bool,
+    /// A second part played in step with the track, as loud as the music intensity allows
+    pub(crate) intensity_layer: Option<Handle<AudioSource>>,
+}
+
+/// Crossfades from whatever music is playing to a track, or to silence with `None`
+#[derive(Event)]
+pub(crate) struct PlayMusic(pub(crate) Option<MusicTrack>);
+
+/// How far a music track has faded in, which it moves toward its target
+///
+/// Looping tracks pause at silence so they pick up where they left off when asked for again,
+/// while anything else is despawned.
+#[derive(Component)]
+pub(crate) struct MusicFade {
+    pub(crate) level: f32,
+    target: f32,
+}
+
+pub(crate) fn crossfade_music(
+    mut commands: Commands,
+    mut play_music_events: EventReader<PlayMusic>,
+    mut music_query: Query<(&AudioPlayer, &PlaybackSettings, &mut MusicFade)>,
+) {
+    // Only the latest request matters when several arrive together
+    let Some(PlayMusic(track)) = play_music_events.read().last() else {
+        return;
+    };
+
+    let mut resumed = Vec::new();
+    for (player, playback, mut fade) in music_query.iter_mut() {
+        // A looping track asked for again resumes rather than starting over
+        let wanted = track.as_ref().is_some_and(|track| {
+            track.looped
+                && (track.source == player.0 || track.intensity_layer.as_ref() == Some(&player.0))
+        });
+        let keep =
+            wanted && matches!(playback.mode, PlaybackMode::Loop) && !resumed.contains(&player.0);
+        fade.target = if keep { 1.0 } else { 0.0 };
+        if keep {
+            resumed.push(player.0.clone());
+        }
+    }
+    let Some(track) = track else {
+        return;
+    };
+    if resumed.contains(&track.source) {
+        return;
+    }
+    let mode = if track.looped {
+        PlaybackMode::Loop
+    } else {
+        PlaybackMode::Despawn
+    };
+    commands.spawn(music_bundle(track.source.clone(), mode, track.volume));
+    if let Some(layer) = &track.intensity_layer {
+        commands.spawn((
+            music_bundle(layer.clone(), mode, 0.0),
+            IntensityLayer {
+                volume: track.volume,
+            },
+        ));
+    }
+}
+
+fn music_bundle(source: Handle<AudioSource>, mode: PlaybackMode, volume: f32) -> impl Bundle {
+    (
+        AudioPlayer::new(source),
+        PlaybackSettings {
+            mode,
+            volume: Volume::new(0.0),
+            ..Default::default()
+        },
+        AudioBus::Music,
+        SoundVolume(volume),
+        MusicFade {
+            level: 0.0,
+            target: 1.0,
+        },
+    )
+}
+
+// Real time so fades keep their length during slow motion
+pub(crate) fn fade_music(
+    mut commands: Commands,
+    mut music_query: Query<(
+        Entity,
+        &PlaybackSettings,
+        &mut MusicFade,
+        Option<&AudioSink>,
+    )>,
+    time: Res<Time<Real>>,
+) {
+    let step = time.delta_secs() / MUSIC_CROSSFADE_DURATION;
+    for (entity, playback, mut fade, sink) in music_query.iter_mut() {
+        if fade.level < fade.target {
+            fade.level = (fade.level + step).min(fade.target);
+            if let Some(sink) = sink.filter(|sink| sink.is_paused()) {
+                sink.play();
+            }
+        } else if fade.level > fade.target {
+            fade.level = (fade.level - step).max(fade.target);
+        } else if fade.target == 0.0 {
+            match sink {
+                Some(sink) if matches!(playback.mode, PlaybackMode::Loop) => {
+                    if !sink.is_paused() {
+                        sink.pause();
+                    }
+                }
+                _ => commands.entity(entity).despawn(),
+            }
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use super::*;
+
+/// How much the gameplay music has built up, from 0.0 at the start of a run to 1.0
+#[derive(Resource, Default)]
+pub(crate) struct MusicIntensity(pub(crate) f32);
+
+/// Music that is only as loud as the current intensity
+#[derive(Component)]
+pub(crate) struct IntensityLayer {
+    pub(crate) volume: f32,
+}
+
+/// Picks the music for the state the app has just entered
+pub(crate) fn play_state_music(
+    app_state: Res<State<AppState>>,
+    audio: Res<AudioAssets>,
+    mut play_music_events: EventWriter<PlayMusic>,
+) {
+    let track = match app_state.get() {
+        AppState::MainMenu
+        | AppState::LevelSelect
+        | AppState::Lobby
+        | AppState::Birds
+        | AppState::Achievements
+        | AppState::Stats
+        | AppState::Settings => Some(MusicTrack {
+            source: audio.menu_music.clone(),
+            volume: MENU_MUSIC_VOLUME,
+            looped: true,
+            intensity_layer: None,
+        }),
+        AppState::Playing => Some(MusicTrack {
+            source: audio.gameplay_music.clone(),
+            volume: GAMEPLAY_MUSIC_VOLUME,
+            looped: true,
+            intensity_layer: Some(audio.gameplay_intensity_music.clone()),
+        }),
+        AppState::RetryMenu => Some(MusicTrack {
+            source: audio.defeat_sting.clone(),
+            volume: DEFEAT_STING_VOLUME,
+            looped: false,
+            intensity_layer: None,
+        }),
+        AppState::Loading | AppState::Editor => None,
+    };
+    play_music_events.send(PlayMusic(track));
+}
+
+pub(crate) fn reset_music_intensity(mut intensity: ResMut<MusicIntensity>) {
+    intensity.0 = 0.0;
+}
+
+/// Eases the intensity toward where the score puts it
+pub(crate) fn follow_score_intensity(
+    mut intensity: ResMut<MusicIntensity>,
+    score: Res<Score>,
+    time: Res<Time<Real>>,
+) {
+    let target = ((score.0 as f32 - INTENSITY_START_SCORE)
+        / (INTENSITY_FULL_SCORE - INTENSITY_START_SCORE))
+        .clamp(0.0, 1.0);
+    let step = time.delta_secs() / INTENSITY_FADE_DURATION;
+    let level = if intensity.0 < target {
+        (intensity.0 + step).min(target)
+    } else {
+        (intensity.0 - step).max(target)
+    };
+    if intensity.0 != level {
+        intensity.0 = level;
+    }
+}
+
+pub(crate) fn apply_music_intensity(
+    mut layer_query: Query<(&IntensityLayer, &mut SoundVolume)>,
+    intensity: Res<MusicIntensity>,
+) {
+    for (layer, mut volume) in layer_query.iter_mut() {
+        volume.0 = layer.volume * intensity.0;
+    }
+}
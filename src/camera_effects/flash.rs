@@ -0,0 +1,53 @@
+use super::*;
+
+/// White sprite in front of the playfield that is faded out after an impact
+#[derive(Component)]
+pub(crate) struct ScreenFlash {
+    alpha: f32,
+}
+
+pub(crate) fn spawn_screen_flash(parent: &mut ChildBuilder) {
+    parent.spawn((
+        Sprite::from_color(
+            Color::WHITE.with_alpha(0.0),
+            // Tall enough to cover the portrait playfield as well
+            Vec2::new(WINDOW_WIDTH_PX, WINDOW_HEIGHT_PX * 2.0),
+        ),
+        Transform::from_xyz(0.0, 0.0, FLASH_Z),
+        Visibility::Hidden,
+        ScreenFlash { alpha: 0.0 },
+    ));
+}
+
+pub(crate) fn start_flash(
+    mut impact_events: EventReader<ScreenImpact>,
+    mut flash_query: Query<&mut ScreenFlash>,
+    settings: Res<Settings>,
+) {
+    for ScreenImpact(strength) in impact_events.read() {
+        if !settings.screen_effects || settings.reduced_motion {
+            continue;
+        }
+        for mut flash in flash_query.iter_mut() {
+            let alpha = strength * settings.flash_intensity * FLASH_MAX_ALPHA;
+            flash.alpha = flash.alpha.max(alpha);
+        }
+    }
+}
+
+pub(crate) fn fade_flash(
+    mut flash_query: Query<(&mut ScreenFlash, &mut Sprite, &mut Visibility)>,
+    time: Res<Time<Real>>,
+) {
+    for (mut flash, mut sprite, mut visibility) in flash_query.iter_mut() {
+        if flash.alpha == 0.0 {
+            if *visibility != Visibility::Hidden {
+                *visibility = Visibility::Hidden;
+            }
+            continue;
+        }
+        sprite.color = Color::WHITE.with_alpha(flash.alpha);
+        *visibility = Visibility::Inherited;
+        flash.alpha = (flash.alpha - FLASH_MAX_ALPHA * time.delta_secs() / FLASH_DURATION).max(0.0);
+    }
+}
@@ -0,0 +1,39 @@
+use super::*;
+
+/// Real seconds left before the game unfreezes after an impact
+#[derive(Resource, Default)]
+pub(crate) struct HitStop(Option<f32>);
+
+pub(crate) fn start_hit_stop(
+    mut impact_events: EventReader<ScreenImpact>,
+    mut hit_stop: ResMut<HitStop>,
+    mut time: ResMut<Time<Virtual>>,
+    settings: Res<Settings>,
+) {
+    for ScreenImpact(strength) in impact_events.read() {
+        if !settings.screen_effects || settings.hit_stop_intensity == 0.0 {
+            continue;
+        }
+        let duration = strength * settings.hit_stop_intensity * HIT_STOP_DURATION;
+        hit_stop.0 = Some(hit_stop.0.unwrap_or(0.0).max(duration));
+        time.pause();
+    }
+}
+
+// Virtual time is what is frozen, so the freeze is timed in real time
+pub(crate) fn end_hit_stop(
+    mut hit_stop: ResMut<HitStop>,
+    mut time: ResMut<Time<Virtual>>,
+    real_time: Res<Time<Real>>,
+) {
+    let Some(remaining) = hit_stop.0 else {
+        return;
+    };
+    let remaining = remaining - real_time.delta_secs();
+    if remaining > 0.0 {
+        hit_stop.0 = Some(remaining);
+    } else {
+        hit_stop.0 = None;
+        time.unpause();
+    }
+}
@@ -0,0 +1,72 @@
+use super::*;
+
+mod flash;
+mod hit_stop;
+mod shake;
+
+// Re-exports
+pub(crate) use flash::*;
+pub(crate) use hit_stop::*;
+pub(crate) use shake::*;
+
+// Impacts
+/// Strength of the impact when the bird crashes
+pub(crate) const CRASH_IMPACT: f32 = 1.0;
+
+// Shake
+/// Furthest the camera is thrown from its place at full trauma
+const SHAKE_MAX_OFFSET_PX: f32 = 14.0;
+/// Furthest the camera is turned at full trauma, in radians
+const SHAKE_MAX_ANGLE: f32 = 0.05;
+/// Trauma lost per second
+const SHAKE_TRAUMA_DECAY: f32 = 1.6;
+/// How quickly the shake wobbles
+const SHAKE_FREQUENCY: f32 = 28.0;
+
+// Flash
+const FLASH_MAX_ALPHA: f32 = 0.7;
+const FLASH_DURATION: f32 = 0.25;
+/// Depth of the flash relative to the camera, above every sprite
+const FLASH_Z: f32 = 3.0;
+
+// Hit-stop
+/// Seconds the game freezes for at full impact
+const HIT_STOP_DURATION: f32 = 0.12;
+
+/// Steps the Settings menu offers for each effect
+pub(crate) const EFFECT_INTENSITY_STEPS: [f32; 5] = [0.0, 0.25, 0.5, 0.75, 1.0];
+
+/// Something hit hard enough to jolt the screen, from 0.0 to 1.0
+#[derive(Event)]
+pub(crate) struct ScreenImpact(pub(crate) f32);
+
+pub(crate) struct CameraEffectsPlugin;
+
+impl Plugin for CameraEffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ScreenImpact>()
+            .init_resource::<HitStop>()
+            .add_systems(
+                Update,
+                (
+                    attach_camera_effects,
+                    (start_shake, start_flash, start_hit_stop),
+                    (shake_camera, fade_flash, end_hit_stop),
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Gives the main camera what its effects need as soon as it is spawned
+pub(crate) fn attach_camera_effects(
+    mut commands: Commands,
+    camera_query: Query<Entity, Added<MainCamera>>,
+) {
+    for camera in camera_query.iter() {
+        commands
+            .entity(camera)
+            .insert(CameraShake::default())
+            .with_children(spawn_screen_flash);
+    }
+}
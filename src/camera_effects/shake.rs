@@ -0,0 +1,53 @@
+use super::*;
+
+/// Trauma-based screen shake, where the shake grows with the square of the trauma
+#[derive(Component, Default)]
+pub(crate) struct CameraShake {
+    trauma: f32,
+    /// Offset applied last frame, taken back off before the next one so the camera keeps its place
+    offset: Vec2,
+}
+
+pub(crate) fn start_shake(
+    mut impact_events: EventReader<ScreenImpact>,
+    mut shake_query: Query<&mut CameraShake>,
+    settings: Res<Settings>,
+) {
+    for ScreenImpact(strength) in impact_events.read() {
+        if !settings.screen_effects || settings.reduced_motion {
+            continue;
+        }
+        for mut shake in shake_query.iter_mut() {
+            shake.trauma = (shake.trauma + strength * settings.shake_intensity).min(1.0);
+        }
+    }
+}
+
+// Real time so the shake plays out during the hit-stop it comes with
+pub(crate) fn shake_camera(
+    mut camera_query: Query<(&mut CameraShake, &mut Transform)>,
+    time: Res<Time<Real>>,
+) {
+    let elapsed = time.elapsed_secs() * SHAKE_FREQUENCY;
+    for (mut shake, mut transform) in camera_query.iter_mut() {
+        if shake.trauma == 0.0 && shake.offset == Vec2::ZERO {
+            continue;
+        }
+        shake.trauma = (shake.trauma - SHAKE_TRAUMA_DECAY * time.delta_secs()).max(0.0);
+        let amount = shake.trauma * shake.trauma;
+
+        // Sines at unrelated frequencies wobble smoothly without repeating a visible pattern
+        let offset = Vec2::new(
+            (elapsed * 1.13).sin() + (elapsed * 2.71).sin() * 0.5,
+            (elapsed * 1.37 + 1.0).sin() + (elapsed * 2.23).sin() * 0.5,
+        ) / 1.5
+            * SHAKE_MAX_OFFSET_PX
+            * amount;
+        let angle = (elapsed * 0.97 + 2.0).sin() * SHAKE_MAX_ANGLE * amount;
+
+        let previous_offset = shake.offset;
+        transform.translation += (offset - previous_offset).extend(0.0);
+        transform.rotation = Quat::from_rotation_z(angle);
+        shake.offset = offset;
+    }
+}
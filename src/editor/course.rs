@@ -0,0 +1,264 @@
+use super::*;
+use bevy::asset::LoadedFolder;
+
+/// A pipe pair being edited, positioned by its distance along the course rather than from the
+/// previous pair so pairs can be dragged past each other
+#[derive(Clone)]
+pub(crate) struct EditorPipe {
+    pub(crate) x: f32,
+    pub(crate) gap_y: f32,
+    pub(crate) gap_size: f32,
+    pub(crate) motion: Option<PipeMotion>,
+}
+
+#[derive(Resource)]
+pub(crate) struct EditorCourse {
+    pub(crate) key: String,
+    pub(crate) slot: usize,
+    source: Option<Handle<Level>>,
+    pub(crate) name: String,
+    par_flaps: u32,
+    pub(crate) pipes: Vec<EditorPipe>,
+    pub(crate) selected: Option<usize>,
+    pub(crate) drag_offset: Option<Vec2>,
+    pub(crate) scroll: f32,
+    pub(crate) unsaved: bool,
+    pub(crate) notice: Option<CourseError>,
+}
+
+/// Why the course could not be flown or saved, shown in the toolbar with details in the log
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum CourseError {
+    NoPipes,
+    InvalidPipe,
+    SaveFailed,
+    /// Web builds have nowhere to write level files
+    #[cfg(target_arch = "wasm32")]
+    NativeOnly,
+}
+
+impl CourseError {
+    pub(crate) fn text_key(self) -> TextKey {
+        match self {
+            CourseError::NoPipes => TextKey::EditorNoPipes,
+            CourseError::InvalidPipe => TextKey::EditorInvalidPipe,
+            CourseError::SaveFailed => TextKey::EditorSaveFailed,
+            #[cfg(target_arch = "wasm32")]
+            CourseError::NativeOnly => TextKey::EditorNativeOnly,
+        }
+    }
+}
+
+impl EditorCourse {
+    /// Opens the level at `slot` in the levels folder, or a blank level one past the end
+    pub(crate) fn open(
+        slot: usize,
+        folder_levels: &[ActiveLevel],
+        levels: &Assets<Level>,
+    ) -> EditorCourse {
+        let slot = slot.min(folder_levels.len());
+        let existing = folder_levels
+            .get(slot)
+            .and_then(|active_level| Some((active_level, levels.get(&active_level.handle)?)));
+
+        match existing {
+            Some((active_level, level)) => {
+                let mut x = 0.0;
+                let pipes = level
+                    .pipes
+                    .iter()
+                    .map(|level_pipe| {
+                        x += level_pipe.distance;
+                        EditorPipe {
+                            x,
+                            gap_y: level_pipe.gap_y,
+                            gap_size: level_pipe.gap_size,
+                            motion: level_pipe.motion,
+                        }
+                    })
+                    .collect();
+                EditorCourse::new(
+                    active_level.key.clone(),
+                    slot,
+                    Some(active_level.handle.clone()),
+                    level.name.clone(),
+                    level.par_flaps,
+                    pipes,
+                )
+            }
+            None => {
+                let key = (slot + 1..)
+                    .map(|number| format!("{number:02}_new_level.level.ron"))
+                    .find(|key| folder_levels.iter().all(|level| level.key != *key))
+                    .unwrap_or_default();
+                EditorCourse::new(
+                    key,
+                    slot,
+                    None,
+                    NEW_LEVEL_NAME.to_string(),
+                    NEW_LEVEL_PAR_FLAPS,
+                    Vec::new(),
+                )
+            }
+        }
+    }
+
+    fn new(
+        key: String,
+        slot: usize,
+        source: Option<Handle<Level>>,
+        name: String,
+        par_flaps: u32,
+        pipes: Vec<EditorPipe>,
+    ) -> EditorCourse {
+        EditorCourse {
+            key,
+            slot,
+            source,
+            name,
+            par_flaps,
+            pipes,
+            selected: None,
+            drag_offset: None,
+            scroll: 0.0,
+            unsaved: false,
+            notice: None,
+        }
+    }
+
+    pub(crate) fn to_level(&self) -> Level {
+        let mut pipes = self.pipes.clone();
+        pipes.sort_by(|a, b| a.x.total_cmp(&b.x));
+
+        let mut previous_x = 0.0;
+        Level {
+            name: self.name.clone(),
+            par_flaps: self.par_flaps,
+            pipes: pipes
+                .into_iter()
+                .map(|pipe| {
+                    let distance = pipe.x - previous_x;
+                    previous_x = pipe.x;
+                    LevelPipe {
+                        distance,
+                        gap_y: pipe.gap_y,
+                        gap_size: pipe.gap_size,
+                        motion: pipe.motion,
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    /// The course as a level, if it is one that can be flown
+    pub(crate) fn playable_level(&self) -> Result<Level, CourseError> {
+        let level = self.to_level();
+        if level.pipes.is_empty() {
+            return Err(CourseError::NoPipes);
+        }
+        level.validate().map_err(|error| {
+            warn!("Could not fly {}: {error}", self.key);
+            CourseError::InvalidPipe
+        })?;
+        Ok(level)
+    }
+
+    /// Index of the pipe pair under a point in course coordinates
+    pub(crate) fn pipe_at(&self, point: Vec2) -> Option<usize> {
+        self.pipes
+            .iter()
+            .position(|pipe| (pipe.x - point.x).abs() <= PIPE_WIDTH_PX / 2.0)
+    }
+
+    pub(crate) fn add_pipe(&mut self, point: Vec2) {
+        self.pipes.push(EditorPipe {
+            x: point.x.max(PIPE_WIDTH_PX),
+            gap_y: clamp_gap_y(point.y, DEFAULT_GAP_SIZE),
+            gap_size: DEFAULT_GAP_SIZE,
+            motion: None,
+        });
+        self.selected = Some(self.pipes.len() - 1);
+        self.unsaved = true;
+    }
+
+    pub(crate) fn move_selected(&mut self, point: Vec2) {
+        if let Some(pipe) = self.selected.and_then(|index| self.pipes.get_mut(index)) {
+            pipe.x = point.x.max(PIPE_WIDTH_PX);
+            pipe.gap_y = clamp_gap_y(point.y, pipe.gap_size);
+            self.unsaved = true;
+        }
+    }
+
+    pub(crate) fn resize_selected(&mut self, step: f32) {
+        if let Some(pipe) = self.selected.and_then(|index| self.pipes.get_mut(index)) {
+            let max_gap_size = WINDOW_MAX_Y / SPRITE_SCALE - 1.0;
+            pipe.gap_size = (pipe.gap_size + step).clamp(MIN_GAP_SIZE, max_gap_size);
+            pipe.gap_y = clamp_gap_y(pipe.gap_y, pipe.gap_size);
+            self.unsaved = true;
+        }
+    }
+
+    pub(crate) fn remove_pipe(&mut self, index: usize) {
+        self.pipes.remove(index);
+        self.selected = None;
+        self.drag_offset = None;
+        self.unsaved = true;
+    }
+
+    /// Writes the course back to its file in `assets/levels/` and refreshes the loaded level
+    pub(crate) fn save(&mut self, levels: &mut Assets<Level>) -> Result<(), CourseError> {
+        let level = self.playable_level()?;
+        let contents = ron::ser::to_string_pretty(&level, Default::default()).map_err(|error| {
+            warn!("Could not write {}: {error}", self.key);
+            CourseError::SaveFailed
+        })?;
+        write_level_file(&self.key, &contents)?;
+
+        if let Some(source) = &self.source {
+            levels.insert(source, level);
+        }
+        self.unsaved = false;
+        Ok(())
+    }
+}
+
+fn clamp_gap_y(gap_y: f32, gap_size: f32) -> f32 {
+    let limit = (WINDOW_MAX_Y / SPRITE_SCALE - gap_size - 1.0).max(0.0);
+    gap_y.clamp(-limit, limit)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_level_file(key: &str, contents: &str) -> Result<(), CourseError> {
+    use bevy::asset::io::file::FileAssetReader;
+
+    let path = FileAssetReader::get_base_path()
+        .join(ASSETS_FOLDER)
+        .join(LEVELS_FOLDER)
+        .join(key);
+    std::fs::write(&path, contents).map_err(|error| {
+        warn!("Could not save {}: {error}", path.display());
+        CourseError::SaveFailed
+    })
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write_level_file(_key: &str, _contents: &str) -> Result<(), CourseError> {
+    Err(CourseError::NativeOnly)
+}
+
+pub(crate) fn open_editor_course(
+    mut commands: Commands,
+    level_assets: Res<LevelAssets>,
+    folders: Res<Assets<LoadedFolder>>,
+    levels: Res<Assets<Level>>,
+) {
+    let folder_levels = folders
+        .get(&level_assets.folder)
+        .map(folder_levels)
+        .unwrap_or_default();
+    commands.insert_resource(EditorCourse::open(0, &folder_levels, &levels));
+}
+
+pub(crate) fn close_editor_course(mut commands: Commands) {
+    commands.remove_resource::<EditorCourse>();
+}
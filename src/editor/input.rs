@@ -0,0 +1,119 @@
+use super::*;
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::window::PrimaryWindow;
+
+pub(crate) fn handle_editor_scroll(
+    mut course: ResMut<EditorCourse>,
+    mut wheel_events: EventReader<MouseWheel>,
+    keys: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+) {
+    let mut scroll = 0.0;
+    if keys.any_pressed(SCROLL_LEFT_KEYS) {
+        scroll -= SCROLL_SPEED * time.delta_secs();
+    }
+    if keys.any_pressed(SCROLL_RIGHT_KEYS) {
+        scroll += SCROLL_SPEED * time.delta_secs();
+    }
+    for event in wheel_events.read() {
+        let lines = match event.unit {
+            MouseScrollUnit::Line => event.y + event.x,
+            MouseScrollUnit::Pixel => (event.y + event.x) / SPRITE_SCALE / WHEEL_SCROLL_STEP,
+        };
+        scroll -= lines * WHEEL_SCROLL_STEP;
+    }
+    if scroll != 0.0 {
+        course.scroll = (course.scroll + scroll).max(0.0);
+    }
+}
+
+pub(crate) fn handle_editor_mouse(
+    mut course: ResMut<EditorCourse>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    interaction_query: Query<&Interaction>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+) {
+    if mouse_buttons.just_released(MouseButton::Left) {
+        course.drag_offset = None;
+    }
+
+    // Clicks on the toolbar should not also edit the course underneath it
+    let over_ui = interaction_query
+        .iter()
+        .any(|interaction| *interaction != Interaction::None);
+    let (Ok(window), Ok((camera, camera_transform))) =
+        (window_query.get_single(), camera_query.get_single())
+    else {
+        return;
+    };
+    let Some(point) = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
+        .map(world_to_course)
+    else {
+        return;
+    };
+
+    if let Some(offset) = course.drag_offset {
+        if mouse_buttons.pressed(MouseButton::Left) {
+            course.move_selected(point + offset);
+        }
+        return;
+    }
+    if over_ui {
+        return;
+    }
+
+    if mouse_buttons.just_pressed(MouseButton::Left) {
+        match course.pipe_at(point) {
+            Some(index) => {
+                let pipe = &course.pipes[index];
+                course.drag_offset = Some(Vec2::new(pipe.x, pipe.gap_y) - point);
+                course.selected = Some(index);
+            }
+            None => {
+                course.add_pipe(point);
+                course.drag_offset = Some(Vec2::ZERO);
+            }
+        }
+    } else if mouse_buttons.just_pressed(MouseButton::Right) {
+        if let Some(index) = course.pipe_at(point) {
+            course.remove_pipe(index);
+        }
+    }
+}
+
+pub(crate) fn handle_editor_keys(
+    mut course: ResMut<EditorCourse>,
+    keys: Res<ButtonInput<KeyCode>>,
+) {
+    if keys.any_just_pressed(DELETE_KEYS) {
+        if let Some(index) = course.selected {
+            course.remove_pipe(index);
+        }
+    }
+    if keys.any_just_pressed(GROW_GAP_KEYS) {
+        course.resize_selected(GAP_SIZE_STEP);
+    }
+    if keys.any_just_pressed(SHRINK_GAP_KEYS) {
+        course.resize_selected(-GAP_SIZE_STEP);
+    }
+}
+
+pub(crate) fn resize_selected_gap(course: &mut EditorCourse, grow: bool) {
+    course.resize_selected(if grow { GAP_SIZE_STEP } else { -GAP_SIZE_STEP });
+}
+
+/// Converts a world position into sprite pixels along the course, measured from the right edge
+/// of the screen when the editor is scrolled to the start
+pub(crate) fn world_to_course(world: Vec2) -> Vec2 {
+    Vec2::new(
+        (world.x + WINDOW_MIN_X) / SPRITE_SCALE,
+        world.y / SPRITE_SCALE,
+    )
+}
+
+pub(crate) fn course_to_world_x(x: f32) -> f32 {
+    x * SPRITE_SCALE - WINDOW_MIN_X
+}
@@ -0,0 +1,49 @@
+use super::*;
+
+mod course;
+mod input;
+mod view;
+
+// Re-exports
+pub(crate) use course::*;
+pub(crate) use input::*;
+pub(crate) use view::*;
+
+// Course
+const NEW_LEVEL_NAME: &str = "New Level";
+const NEW_LEVEL_PAR_FLAPS: u32 = 30;
+const DEFAULT_GAP_SIZE: f32 = 15.0;
+const MIN_GAP_SIZE: f32 = 6.0;
+const GAP_SIZE_STEP: f32 = 1.0;
+
+// Input
+const SCROLL_SPEED: f32 = 120.0;
+const WHEEL_SCROLL_STEP: f32 = 16.0;
+const SCROLL_LEFT_KEYS: [KeyCode; 2] = [KeyCode::ArrowLeft, KeyCode::KeyA];
+const SCROLL_RIGHT_KEYS: [KeyCode; 2] = [KeyCode::ArrowRight, KeyCode::KeyD];
+const DELETE_KEYS: [KeyCode; 2] = [KeyCode::Delete, KeyCode::Backspace];
+const GROW_GAP_KEYS: [KeyCode; 2] = [KeyCode::Equal, KeyCode::NumpadAdd];
+const SHRINK_GAP_KEYS: [KeyCode; 2] = [KeyCode::Minus, KeyCode::NumpadSubtract];
+
+// View
+const SELECTED_PIPE_COLOR: [f32; 3] = [1.0, 0.75, 0.4]; // #ffbf66
+const START_MARKER_ALPHA: f32 = 0.5;
+
+pub(crate) struct EditorPlugin;
+
+impl Plugin for EditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                handle_editor_scroll,
+                handle_editor_mouse,
+                handle_editor_keys,
+                sync_editor_sprites,
+                update_editor_camera,
+            )
+                .chain()
+                .run_if(in_state(AppState::Editor)),
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use super::*;
+
+#[derive(Component)]
+pub(crate) struct EditorSprite;
+
+pub(crate) fn sync_editor_sprites(
+    mut commands: Commands,
+    course: Res<EditorCourse>,
+    editor_sprite_query: Query<Entity, With<EditorSprite>>,
+    sprites: Res<SpriteAssets>,
+) {
+    if !course.is_changed() && !editor_sprite_query.is_empty() {
+        return;
+    }
+    for entity in editor_sprite_query.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    for (index, pipe) in course.pipes.iter().enumerate() {
+        let color = if course.selected == Some(index) {
+            Color::srgb_from_array(SELECTED_PIPE_COLOR)
+        } else {
+            Color::WHITE
+        };
+        for direction in [PIPE_DIRECTION_UP, PIPE_DIRECTION_DOWN] {
+            let y = pipe_translation_y(
+                pipe.gap_y * SPRITE_SCALE,
+                pipe.gap_size * SPRITE_SCALE,
+                direction,
+            );
+            commands.spawn((
+                Sprite {
+                    image: sprites.pipe_sprite.clone(),
+                    color,
+                    ..Default::default()
+                },
+                Transform::from_xyz(course_to_world_x(pipe.x), y, PIPE_SPRITE_Z)
+                    .with_scale(Vec3::new(SPRITE_SCALE, SPRITE_SCALE * -direction, 1.0)),
+                EditorSprite,
+            ));
+        }
+    }
+
+    let last_x = course.pipes.iter().map(|pipe| pipe.x).fold(0.0, f32::max);
+    commands.spawn((
+        FinishLineBundle::new(course_to_world_x(last_x + FINISH_LINE_DISTANCE)),
+        EditorSprite,
+    ));
+
+    // Marks where the bird starts when playtesting from the current scroll position
+    commands.spawn((
+        Sprite {
+            image: sprites.player_sprite.clone(),
+            color: Color::WHITE.with_alpha(START_MARKER_ALPHA),
+            ..Default::default()
+        },
+        Transform::from_xyz(course.scroll * SPRITE_SCALE, 0.0, PLAYER_SPRITE_Z)
+            .with_scale(Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.0)),
+        EditorSprite,
+    ));
+}
+
+pub(crate) fn update_editor_camera(
+    course: Res<EditorCourse>,
+    mut camera_query: Query<&mut Transform, With<Camera2d>>,
+) {
+    if let Ok(mut camera_transform) = camera_query.get_single_mut() {
+        camera_transform.translation.x = course.scroll * SPRITE_SCALE;
+    }
+}
+
+pub(crate) fn reset_editor_camera(mut camera_query: Query<&mut Transform, With<Camera2d>>) {
+    if let Ok(mut camera_transform) = camera_query.get_single_mut() {
+        camera_transform.translation.x = 0.0;
+    }
+}
+
+pub(crate) fn despawn_editor_sprites(
+    mut commands: Commands,
+    editor_sprite_query: Query<Entity, With<EditorSprite>>,
+) {
+    for entity in editor_sprite_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
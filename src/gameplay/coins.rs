@@ -0,0 +1,102 @@
+use super::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+#[derive(Component)]
+pub(crate) struct Coin;
+
+/// Coins collected during the current run, added to the [`Wallet`] once it ends
+#[derive(Resource, Default)]
+pub(crate) struct RunCoins(pub(crate) u32);
+
+#[derive(Resource, Serialize, Deserialize, Default)]
+pub(crate) struct Wallet {
+    pub(crate) coins: u64,
+}
+
+impl Saved for Wallet {
+    const KEY: &'static str = "wallet";
+}
+
+pub(crate) fn spawn_coin(commands: &mut Commands, x: f32, y: f32, sprites: &SpriteAssets) {
+    commands.spawn((
+        Coin,
+        Sprite {
+            image: sprites.coin_sprite.clone(),
+            ..Default::default()
+        },
+        Transform::from_xyz(x, y, PICKUP_SPRITE_Z).with_scale(Vec3::new(
+            SPRITE_SCALE,
+            SPRITE_SCALE,
+            1.0,
+        )),
+    ));
+}
+
+/// Rolls coins for an endless pair, one in an empty gap and one on the way to the next pair
+pub(crate) fn spawn_endless_coins(
+    commands: &mut Commands,
+    x: f32,
+    gap_y: f32,
+    gap_is_empty: bool,
+    tuning: &GameTuning,
+    rand: &mut impl Rng,
+    sprites: &SpriteAssets,
+) {
+    if gap_is_empty && rand.gen_bool(GAP_COIN_CHANCE) {
+        spawn_coin(commands, x, gap_y, sprites);
+    }
+    if rand.gen_bool(TRAIL_COIN_CHANCE) {
+        spawn_coin(
+            commands,
+            x + tuning.pipe_spacing_scaled() / 2.0,
+            gap_y,
+            sprites,
+        );
+    }
+}
+
+pub(crate) fn handle_coin_pickups(
+    mut commands: Commands,
+    player_query: Query<&Transform, With<Player>>,
+    coin_query: Query<(Entity, &Transform), With<Coin>>,
+    mut run_coins: ResMut<RunCoins>,
+    power_ups: Res<ActivePowerUps>,
+    mut play_sound_events: EventWriter<PlaySound>,
+    tuning: Res<GameTuning>,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    let player_collision_radius = tuning.player_collision_radius() * power_ups.player_scale();
+    for (entity, coin_transform) in coin_query.iter() {
+        if !circle_rectangle_collision(
+            player_transform,
+            player_collision_radius,
+            coin_transform,
+            COIN_SIZE_SCALED,
+            COIN_SIZE_SCALED,
+        ) {
+            continue;
+        }
+        run_coins.0 += 1;
+        commands.entity(entity).despawn();
+        play_sound_events.send(PlaySound(SoundEffect::Coin));
+    }
+}
+
+pub(crate) fn reset_run_coins(mut run_coins: ResMut<RunCoins>) {
+    run_coins.0 = 0;
+}
+
+// Playtests would otherwise let the editor print money
+pub(crate) fn deposit_run_coins(
+    run_coins: Res<RunCoins>,
+    game_mode: Res<GameMode>,
+    mut wallet: ResMut<Wallet>,
+) {
+    if run_coins.0 == 0 || matches!(*game_mode, GameMode::Playtest { .. }) {
+        return;
+    }
+    wallet.coins += run_coins.0 as u64;
+}
@@ -0,0 +1,53 @@
+use super::*;
+
+/// Asks for the bird to flap, whether from a player, the autopilot or a replay
+#[derive(Event)]
+pub(crate) struct FlapRequested;
+
+/// Lets the game fly the bird on its own
+#[derive(Resource, Default)]
+pub(crate) struct Autoplay(pub(crate) bool);
+
+// Replays own the bird, so keys and taps are ignored while one plays
+pub(crate) fn read_flap_input(
+    keys: Res<ButtonInput<KeyCode>>,
+    touches: Res<Touches>,
+    playback: Option<Res<ReplayPlayback>>,
+    mut flap_events: EventWriter<FlapRequested>,
+) {
+    let pressed = keys.just_pressed(FLAP_KEY) || touches.any_just_pressed();
+    if pressed && playback.is_none() {
+        flap_events.send(FlapRequested);
+    }
+}
+
+/// Flaps whenever the bird falls below the top of the next bottom pipe
+pub(crate) fn autoplay_flaps(
+    player_query: Query<(&Player, &Transform)>,
+    pipe_query: Query<(&Pipe, &Transform)>,
+    player_state: Res<State<PlayerState>>,
+    mut flap_events: EventWriter<FlapRequested>,
+) {
+    let Ok((player, player_transform)) = player_query.get_single() else {
+        return;
+    };
+    if *player_state.get() == PlayerState::WaitingToStart {
+        flap_events.send(FlapRequested);
+        return;
+    }
+    let player_x = player_transform.translation.x;
+    let next_gap_bottom = pipe_query
+        .iter()
+        .filter(|(pipe, transform)| {
+            !pipe.is_top() && transform.translation.x + PIPE_HALF_WIDTH_SCALED > player_x
+        })
+        .min_by(|(_, a), (_, b)| a.translation.x.total_cmp(&b.translation.x))
+        .map_or(0.0, |(_, transform)| {
+            transform.translation.y + PIPE_HEIGHT_SCALED / 2.0
+        });
+    if player.velocity <= 0.0
+        && player_transform.translation.y < next_gap_bottom + AUTOPLAY_CLEARANCE
+    {
+        flap_events.send(FlapRequested);
+    }
+}
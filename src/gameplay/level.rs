@@ -0,0 +1,185 @@
+use super::*;
+use bevy::asset::LoadedFolder;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Resource, Default, Clone)]
+pub(crate) enum GameMode {
+    #[default]
+    Endless,
+    Level(ActiveLevel),
+    /// An unsaved level from the editor, started part way along the course
+    Playtest {
+        level: Handle<Level>,
+        start_distance: f32,
+    },
+}
+
+#[derive(Clone)]
+pub(crate) struct ActiveLevel {
+    pub(crate) handle: Handle<Level>,
+    /// File name of the level, used to key its saved progress
+    pub(crate) key: String,
+}
+
+#[derive(Component)]
+pub(crate) struct FinishLine;
+
+#[derive(Bundle)]
+pub(crate) struct FinishLineBundle {
+    finish_line: FinishLine,
+    sprite: Sprite,
+    transform: Transform,
+}
+
+#[derive(Resource, Default)]
+pub(crate) struct LevelOutcome {
+    /// Stars earned for finishing the level, `None` until the finish line is crossed
+    pub(crate) stars: Option<u8>,
+}
+
+#[derive(Resource, Default, Serialize, Deserialize)]
+pub(crate) struct LevelProgress(HashMap<String, LevelRecord>);
+
+#[derive(Serialize, Deserialize, Default, Clone, Copy)]
+pub(crate) struct LevelRecord {
+    pub(crate) best_score: u32,
+    pub(crate) stars: u8,
+    /// Whether the best score was set with the speed assist or forgiving collisions on
+    #[serde(default)]
+    pub(crate) assisted: bool,
+    /// Whether the most stars were earned with an assist on
+    #[serde(default)]
+    pub(crate) stars_assisted: bool,
+}
+
+impl GameMode {
+    pub(crate) fn level(&self) -> Option<&Handle<Level>> {
+        match self {
+            GameMode::Endless => None,
+            GameMode::Level(active_level) => Some(&active_level.handle),
+            GameMode::Playtest { level, .. } => Some(level),
+        }
+    }
+}
+
+impl Saved for LevelProgress {
+    const KEY: &'static str = "level_progress";
+}
+
+impl LevelProgress {
+    pub(crate) fn record(&self, key: &str) -> LevelRecord {
+        self.0.get(key).copied().unwrap_or_default()
+    }
+}
+
+impl FinishLineBundle {
+    pub(crate) fn new(x: f32) -> FinishLineBundle {
+        FinishLineBundle {
+            sprite: Sprite::from_color(
+                Color::WHITE.with_alpha(FINISH_LINE_ALPHA),
+                Vec2::new(FINISH_LINE_WIDTH_PX, WINDOW_HEIGHT_PX),
+            ),
+            transform: Transform::from_xyz(x, 0.0, PIPE_SPRITE_Z),
+            finish_line: FinishLine,
+        }
+    }
+}
+
+pub(crate) fn level_stars(flaps: u32, par_flaps: u32) -> u8 {
+    if flaps <= par_flaps {
+        3
+    } else if flaps <= par_flaps + par_flaps / 2 {
+        2
+    } else {
+        1
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn update_finish_line(
+    mut commands: Commands,
+    mut finish_line_query: Query<&mut Transform, With<FinishLine>>,
+    player_query: Query<&Transform, (With<Player>, Without<FinishLine>)>,
+    mut outcome: ResMut<LevelOutcome>,
+    game_mode: Res<GameMode>,
+    levels: Res<Assets<Level>>,
+    run_totals: RunTotals,
+    mut run_ended_events: EventWriter<RunEnded>,
+    tuning: Res<GameTuning>,
+    time: Res<Time>,
+    mut run_over: ResMut<RunOver>,
+    mut next_player_state: ResMut<NextState<PlayerState>>,
+) {
+    let Ok(mut transform) = finish_line_query.get_single_mut() else {
+        return;
+    };
+    transform.translation.x -= time.delta_secs() * tuning.pipe_speed;
+
+    if transform.translation.x > 0.0 || run_over.0 {
+        return;
+    }
+    run_over.0 = true;
+    let par_flaps = game_mode
+        .level()
+        .and_then(|level| levels.get(level))
+        .map_or(0, |level| level.par_flaps);
+    outcome.stars = Some(level_stars(run_totals.flaps.0, par_flaps));
+    next_player_state.set(PlayerState::Finished);
+    commands.spawn(ResetDelayTimer::new(LEVEL_FINISH_DELAY));
+    let height = player_query
+        .get_single()
+        .map_or(0.0, |player_transform| player_transform.translation.y);
+    run_ended_events.send(run_totals.run_ended(height, tuning.pipe_speed, None));
+}
+
+pub(crate) fn record_level_result(
+    game_mode: Res<GameMode>,
+    outcome: Res<LevelOutcome>,
+    score: Res<Score>,
+    assists: Res<RunAssists>,
+    mut progress: ResMut<LevelProgress>,
+) {
+    let GameMode::Level(active_level) = &*game_mode else {
+        return;
+    };
+    let record = progress.0.entry(active_level.key.clone()).or_default();
+    let assisted = assists.is_assisted();
+    improve_best(
+        &mut record.best_score,
+        &mut record.assisted,
+        score.0,
+        assisted,
+    );
+    if let Some(stars) = outcome.stars {
+        improve_best(
+            &mut record.stars,
+            &mut record.stars_assisted,
+            stars,
+            assisted,
+        );
+    }
+}
+
+/// Raises a best result and its assisted mark, where matching an assisted best without help clears the mark
+fn improve_best<T: Ord>(best: &mut T, best_assisted: &mut bool, value: T, assisted: bool) {
+    if value > *best || (value == *best && !assisted) {
+        *best_assisted = assisted;
+        *best = value;
+    }
+}
+
+/// Lists the levels in the loaded `levels` folder, ordered by file name
+pub(crate) fn folder_levels(folder: &LoadedFolder) -> Vec<ActiveLevel> {
+    let mut levels: Vec<ActiveLevel> = folder
+        .handles
+        .iter()
+        .filter_map(|handle| {
+            let key = handle.path()?.path().file_name()?.to_str()?.to_string();
+            let handle = handle.clone().try_typed::<Level>().ok()?;
+            Some(ActiveLevel { handle, key })
+        })
+        .collect();
+    levels.sort_by(|a, b| a.key.cmp(&b.key));
+    levels
+}
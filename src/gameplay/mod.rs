@@ -1,46 +1,76 @@
 use super::*;
 
+mod coins;
+mod controls;
+mod level;
 mod pipes;
 mod player;
+mod power_ups;
+mod replay;
+mod run;
+mod seed;
+mod skins;
 
 // Re-exports
+pub(crate) use coins::*;
+pub(crate) use controls::*;
+pub(crate) use level::*;
 pub(crate) use pipes::*;
 pub(crate) use player::*;
+pub(crate) use power_ups::*;
+pub(crate) use replay::*;
+pub(crate) use run::*;
+pub(crate) use seed::*;
+pub(crate) use skins::*;
 
 // Pipes
-const PIPE_AMOUNT: i32 = 4;
-const PIPE_WIDTH_PX: f32 = 18.0;
-const PIPE_HEIGHT_PX: f32 = 144.0;
+pub(crate) const PIPE_WIDTH_PX: f32 = 18.0;
+pub(crate) const PIPE_HEIGHT_PX: f32 = 144.0;
 const PIPE_WIDTH_SCALED: f32 = PIPE_WIDTH_PX * SPRITE_SCALE;
-const PIPE_HALF_WIDTH_SCALED: f32 = PIPE_WIDTH_SCALED / 2.0;
-const PIPE_HEIGHT_SCALED: f32 = PIPE_HEIGHT_PX * SPRITE_SCALE;
-const PIPE_GAP_SIZE: f32 = 15.0;
-const PIPE_VERTICAL_CENTER: f32 = (PIPE_HEIGHT_PX / 2.0 + PIPE_GAP_SIZE) * SPRITE_SCALE;
-const PIPE_VERTICAL_OFFSET: f32 = 30.0;
-pub(crate) const PIPE_SPACING: f32 = 60.0;
-pub(crate) const PIPE_SPEED: f32 = 150.0;
+pub(crate) const PIPE_HALF_WIDTH_SCALED: f32 = PIPE_WIDTH_SCALED / 2.0;
+pub(crate) const PIPE_HEIGHT_SCALED: f32 = PIPE_HEIGHT_PX * SPRITE_SCALE;
 
-const PIPE_DIRECTION_UP: f32 = 1.0;
-const PIPE_DIRECTION_DOWN: f32 = -1.0;
+pub(crate) const PIPE_DIRECTION_UP: f32 = 1.0;
+pub(crate) const PIPE_DIRECTION_DOWN: f32 = -1.0;
+
+// Levels
+pub(crate) const FINISH_LINE_DISTANCE: f32 = 60.0;
+const FINISH_LINE_WIDTH_PX: f32 = 16.0;
+const FINISH_LINE_ALPHA: f32 = 0.6;
+const LEVEL_FINISH_DELAY: f32 = 1.0;
+
+// Power-ups
+const POWER_UP_CHANCE: f64 = 0.15;
+const POWER_UP_SIZE_PX: f32 = 8.0;
+const POWER_UP_SIZE_SCALED: f32 = POWER_UP_SIZE_PX * SPRITE_SCALE;
+const SHIELD_DURATION: f32 = 10.0;
+const SHIELD_GRACE_DURATION: f32 = 0.75;
+const SLOW_MOTION_DURATION: f32 = 5.0;
+const SLOW_MOTION_SPEED: f32 = 0.6;
+const SHRINK_DURATION: f32 = 8.0;
+const SHRINK_FACTOR: f32 = 0.6;
+const DOUBLE_SCORE_DURATION: f32 = 10.0;
+
+// Coins
+const COIN_SIZE_PX: f32 = 6.0;
+const COIN_SIZE_SCALED: f32 = COIN_SIZE_PX * SPRITE_SCALE;
+const GAP_COIN_CHANCE: f64 = 0.5;
+const TRAIL_COIN_CHANCE: f64 = 0.3;
+
+// Pickups scrolled past the left edge by more than this are despawned
+const PICKUP_DESPAWN_MARGIN: f32 = POWER_UP_SIZE_SCALED;
 
 // Player
-const PLAYER_WIDTH_PX: f32 = 12.0;
-const PLAYER_WIDTH_SCALED: f32 = PLAYER_WIDTH_PX * SPRITE_SCALE;
-const PLAYER_COLLISION_RADIUS: f32 = PLAYER_WIDTH_SCALED / 2.0;
-const PLAYER_COLLISION_FACTOR: f32 = 0.5;
-const PLAYER_COLLISION_RADIUS_FACTORED: f32 = PLAYER_COLLISION_RADIUS * PLAYER_COLLISION_FACTOR;
+pub(crate) const PLAYER_WIDTH_PX: f32 = 12.0;
+pub(crate) const PLAYER_HEIGHT_PX: f32 = 8.0;
 
 const PLAYER_AUTO_DESPAWN_DISTANCE: f32 = 100.0;
 
-const PLAYER_FLAP_FORCE: f32 = 500.0;
-const PLAYING_GRAVITY_STRENGTH: f32 = 1800.0;
-const ANIMATION_GRAVITY_STRENGTH: f32 = 750.0;
-const VELOCITY_TO_ROTATION_RATIO: f32 = 13.0;
-
 const FLAP_KEY: KeyCode = KeyCode::Space;
 
-const FALL_SOUND_DELAY: f32 = 0.5;
-const FALL_RESET_DELAY: f32 = 1.75;
+// Autoplay
+/// Height above the next bottom pipe the autopilot keeps the bird at
+const AUTOPLAY_CLEARANCE: f32 = 20.0;
 
 pub(crate) struct GameplayPlugin;
 
@@ -48,30 +78,98 @@ impl Plugin for GameplayPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             FixedUpdate,
-            update_pipe_transforms.run_if(in_state(PlayerState::Flapping)),
+            (
+                update_pipe_transforms,
+                update_moving_pipes,
+                update_pickup_transforms,
+                update_finish_line,
+                handle_pipe_scoring,
+            )
+                .chain()
+                .run_if(in_state(PlayerState::Flapping)),
+        )
+        // Flaps land on a fixed tick so a replay can put each one back on the tick it was flown
+        .add_systems(
+            FixedUpdate,
+            (
+                play_replay_flaps.run_if(resource_exists::<ReplayPlayback>),
+                handle_flaps,
+            )
+                .chain()
+                .run_if(in_state(AppState::Playing))
+                .before(update_pipe_transforms),
         )
         .add_systems(Startup, |mut commands: Commands| {
             commands.insert_resource(Score(0));
         })
+        .init_resource::<RunSeed>()
+        .init_resource::<PipeRng>()
+        .init_resource::<GameMode>()
+        .init_resource::<Flaps>()
+        .init_resource::<LevelOutcome>()
+        .init_resource::<ActivePowerUps>()
+        .init_resource::<RunCoins>()
+        .init_resource::<RunClock>()
+        .init_resource::<PipesPassed>()
+        .init_resource::<RunOver>()
+        .init_resource::<ScoreTimer>()
+        .add_event::<RunEnded>()
+        .init_resource::<RunTicks>()
+        .init_resource::<FixedSeed>()
+        .init_resource::<Autoplay>()
+        .init_resource::<RecordedFlaps>()
+        .add_event::<FlapRequested>()
+        .init_saved::<Replay>()
+        .add_systems(Update, save_replay)
+        .init_saved::<Wallet>()
+        .init_saved::<SkinCollection>()
+        .add_systems(
+            Update,
+            apply_equipped_skin.run_if(resource_changed::<SkinCollection>),
+        )
+        .init_saved::<LevelProgress>()
         .init_state::<PlayerState>()
+        .add_systems(
+            OnEnter(AppState::Playing),
+            (
+                reset_run_state,
+                reset_score_timer,
+                reset_power_ups,
+                reset_run_coins,
+                rewind_replay,
+            ),
+        )
         .add_systems(
             Update,
             (
-                handle_frozen_toggle.run_if(in_state(PlayerState::WaitingToStart)),
-                handle_player_input.run_if(in_state(PlayerState::Flapping)),
+                (
+                    read_flap_input,
+                    autoplay_flaps.run_if(|autoplay: Res<Autoplay>| autoplay.0),
+                )
+                    .run_if(in_state(AppState::Playing)),
                 handle_fall_sound_delay_timer.run_if(in_state(PlayerState::WaitingToFall)),
-                handle_fall_reset_delay_timer.run_if(in_state(PlayerState::Falling)),
+                handle_fall_reset_delay_timer
+                    .run_if(in_state(PlayerState::Falling).or(in_state(PlayerState::Finished))),
                 handle_fall_animation.run_if(in_state(PlayerState::Falling)),
                 handle_auto_despawn.run_if(in_state(PlayerState::Falling)),
+                apply_game_speed,
             ),
         )
         .add_systems(
             FixedUpdate,
             (
-                update_player_transform.run_if(in_state(PlayerState::Flapping)),
-                handle_player_collision.run_if(in_state(PlayerState::Flapping)),
+                tick_run_clock,
+                tick_power_ups,
+                update_player_transform,
+                update_player_scale,
+                handle_power_up_pickups,
+                handle_coin_pickups,
+                handle_player_collision,
             )
-                .chain(),
+                .chain()
+                .run_if(in_state(PlayerState::Flapping))
+                // Collide against where moving pipes are this tick rather than the last one
+                .after(handle_pipe_scoring),
         );
     }
 }
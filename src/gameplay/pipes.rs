@@ -1,11 +1,25 @@
 use super::*;
-use bevy::window::PrimaryWindow;
-use rand::rngs::ThreadRng;
-use rand::{thread_rng, Rng};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::f32::consts::TAU;
+use std::time::Duration;
 
 #[derive(Component)]
 pub(crate) struct Pipe {
     direction: f32,
+    gap_y: f32,
+    gap_size: f32,
+    passed: bool,
+}
+
+/// Scores endless runs once for every pipe pair's worth of time flown
+#[derive(Resource, Default)]
+pub(crate) struct ScoreTimer(Timer);
+
+#[derive(Component)]
+pub(crate) struct MovingPipe {
+    motion: PipeMotion,
+    elapsed: f32,
 }
 
 #[derive(Bundle)]
@@ -16,79 +30,388 @@ struct PipeBundle {
 }
 
 impl PipeBundle {
-    fn new(translation: Vec2, direction: f32, pipe_image: &Handle<Image>) -> PipeBundle {
+    fn new(
+        x: f32,
+        gap_y: f32,
+        gap_size: f32,
+        direction: f32,
+        pipe_image: &Handle<Image>,
+    ) -> PipeBundle {
+        let pipe = Pipe {
+            direction,
+            gap_y,
+            gap_size,
+            passed: false,
+        };
         PipeBundle {
             sprite: Sprite {
                 image: pipe_image.clone(),
                 ..Default::default()
             },
-            transform: Transform::from_translation(translation.extend(PIPE_SPRITE_Z))
+            transform: Transform::from_xyz(x, pipe.translation_y(0.0, 0.0), PIPE_SPRITE_Z)
                 .with_scale(Vec3::new(SPRITE_SCALE, SPRITE_SCALE * -direction, 1.0)),
-            pipe: Pipe { direction },
+            pipe,
+        }
+    }
+}
+
+impl Pipe {
+    /// Whether this is the upper pipe of its pair
+    pub(crate) fn is_top(&self) -> bool {
+        self.direction == PIPE_DIRECTION_UP
+    }
+
+    fn translation_y(&self, gap_offset: f32, closing: f32) -> f32 {
+        pipe_translation_y(
+            self.gap_y + gap_offset,
+            self.gap_size - closing,
+            self.direction,
+        )
+    }
+}
+
+/// Height of the center of a pipe facing `direction` around a gap of half height `gap_size`
+pub(crate) fn pipe_translation_y(gap_y: f32, gap_size: f32, direction: f32) -> f32 {
+    gap_y + direction * (PIPE_HEIGHT_SCALED / 2.0 + gap_size)
+}
+
+impl PipeMotion {
+    /// Vertical offset of the gap and how far each pipe has closed in towards it, `elapsed`
+    /// seconds after the pair was placed and with the pair currently at `x`
+    fn gap_shift(&self, elapsed: f32, x: f32) -> (f32, f32) {
+        match *self {
+            PipeMotion::Oscillate { amplitude, period } => (
+                amplitude * SPRITE_SCALE * (elapsed / period * TAU).sin(),
+                0.0,
+            ),
+            PipeMotion::Jaws { closing, period } => {
+                let closed = 0.5 - 0.5 * (elapsed / period * TAU).cos();
+                (0.0, closing * SPRITE_SCALE * closed)
+            }
+            PipeMotion::SlideIn { offset, distance } => {
+                let on_screen = WINDOW_MAX_X + PIPE_HALF_WIDTH_SCALED - x;
+                let remaining = 1.0 - (on_screen / (distance * SPRITE_SCALE)).clamp(0.0, 1.0);
+                (offset * SPRITE_SCALE * remaining * remaining, 0.0)
+            }
         }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn update_pipe_transforms(
-    mut pipe_query: Query<(&mut Pipe, &mut Transform)>,
+    mut commands: Commands,
+    mut pipe_query: Query<(Entity, &mut Pipe, &mut Transform)>,
+    mut pipe_rng: ResMut<PipeRng>,
+    game_mode: Res<GameMode>,
+    pipes_passed: Res<PipesPassed>,
+    sprites: Res<SpriteAssets>,
+    tuning: Res<GameTuning>,
+    playfield: Res<Playfield>,
     time: Res<Time>,
 ) {
-    let mut pipes_to_reset = Vec::new();
+    for (_, _, mut transform) in pipe_query.iter_mut() {
+        transform.translation.x -= time.delta_secs() * tuning.pipe_speed;
+    }
+
+    // Level courses are laid out in full up front and simply scroll past
+    let GameMode::Endless = *game_mode else {
+        return;
+    };
+
+    let recycle = pipe_query
+        .iter()
+        .any(|(_, _, transform)| transform.translation.x + PIPE_HALF_WIDTH_SCALED < WINDOW_MIN_X);
+    if !recycle {
+        return;
+    }
 
-    for (pipe, transform) in pipe_query.iter() {
-        if transform.translation.x + PIPE_WIDTH_SCALED / 2.0 < WINDOW_MIN_X {
-            pipes_to_reset.push(pipe.direction);
+    let y_offset = generate_pipe_offset(&mut pipe_rng.0, &tuning, &playfield);
+    let motion = choose_pipe_motion(&mut pipe_rng.0, &tuning, pipes_passed.0);
+    let mut recycled_x = None;
+    for (entity, mut pipe, mut transform) in pipe_query.iter_mut() {
+        if transform.translation.x + PIPE_HALF_WIDTH_SCALED < WINDOW_MIN_X {
+            pipe.gap_y = y_offset;
+            pipe.passed = false;
+            transform.translation.x += tuning.pipe_amount as f32 * tuning.pipe_spacing_scaled();
+            recycled_x = Some(transform.translation.x);
+            transform.translation.y = pipe.translation_y(0.0, 0.0);
+            match motion {
+                Some(motion) => commands.entity(entity).insert(MovingPipe {
+                    motion,
+                    elapsed: 0.0,
+                }),
+                None => commands.entity(entity).remove::<MovingPipe>(),
+            };
         }
     }
 
-    if !pipes_to_reset.is_empty() {
-        let mut rand = thread_rng();
-        let y_offset = generate_pipe_offset(&mut rand);
+    if let Some(x) = recycled_x {
+        spawn_gap_pickups(
+            &mut commands,
+            x,
+            y_offset,
+            motion,
+            &tuning,
+            &mut pipe_rng.0,
+            &sprites,
+        );
+    }
+}
 
-        for (pipe, mut transform) in pipe_query.iter_mut() {
-            transform.translation.x -= time.delta_secs() * PIPE_SPEED;
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_pickup_transforms(
+    mut commands: Commands,
+    mut pickup_query: Query<(Entity, &mut Transform), Or<(With<PowerUpPickup>, With<Coin>)>>,
+    tuning: Res<GameTuning>,
+    time: Res<Time>,
+) {
+    for (entity, mut transform) in pickup_query.iter_mut() {
+        transform.translation.x -= time.delta_secs() * tuning.pipe_speed;
+        if transform.translation.x + PICKUP_DESPAWN_MARGIN < WINDOW_MIN_X {
+            commands.entity(entity).despawn();
+        }
+    }
+}
 
-            if transform.translation.x + PIPE_WIDTH_SCALED / 2.0 < WINDOW_MIN_X {
-                transform.translation.x += PIPE_AMOUNT as f32 * PIPE_SPACING * SPRITE_SCALE;
-                transform.translation.y = PIPE_VERTICAL_CENTER * pipe.direction + y_offset;
+pub(crate) fn update_moving_pipes(
+    mut pipe_query: Query<(&Pipe, &mut MovingPipe, &mut Transform)>,
+    time: Res<Time>,
+) {
+    for (pipe, mut moving_pipe, mut transform) in pipe_query.iter_mut() {
+        moving_pipe.elapsed += time.delta_secs();
+        let (gap_offset, closing) = moving_pipe
+            .motion
+            .gap_shift(moving_pipe.elapsed, transform.translation.x);
+        transform.translation.y = pipe.translation_y(gap_offset, closing);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn handle_pipe_scoring(
+    mut pipe_query: Query<(&mut Pipe, &Transform)>,
+    player_query: Query<&Transform, With<Player>>,
+    mut score: ResMut<Score>,
+    mut pipes_passed: ResMut<PipesPassed>,
+    mut score_timer: ResMut<ScoreTimer>,
+    game_mode: Res<GameMode>,
+    power_ups: Res<ActivePowerUps>,
+    mut play_sound_events: EventWriter<PlaySound>,
+    mut emit_particles_events: EventWriter<EmitParticles>,
+    time: Res<Time>,
+) {
+    let endless = matches!(*game_mode, GameMode::Endless);
+    let mut points = 0;
+    for (mut pipe, transform) in pipe_query.iter_mut() {
+        // Only the upper pipe of each pair counts towards the score
+        if pipe.passed || !pipe.is_top() {
+            continue;
+        }
+        if transform.translation.x + PIPE_HALF_WIDTH_SCALED < 0.0 {
+            pipe.passed = true;
+            pipes_passed.0 += 1;
+            if !endless {
+                points += 1;
             }
         }
-    } else {
-        for (_, mut transform) in pipe_query.iter_mut() {
-            transform.translation.x -= time.delta_secs() * PIPE_SPEED;
+    }
+    // Endless runs keep scoring on the timer rather than on the pipes themselves
+    if endless {
+        points += score_timer.0.tick(time.delta()).times_finished_this_tick();
+    }
+
+    for _ in 0..points {
+        score.0 += power_ups.score_multiplier();
+        play_sound_events.send(PlaySound(SoundEffect::Score));
+        if let Ok(player_transform) = player_query.get_single() {
+            emit_particles_events.send(EmitParticles {
+                burst: ParticleBurst::Pass,
+                position: player_transform.translation.truncate(),
+            });
         }
     }
 }
 
+pub(crate) fn reset_score_timer(mut score_timer: ResMut<ScoreTimer>, tuning: Res<GameTuning>) {
+    score_timer.0 = Timer::new(
+        Duration::from_secs_f32(tuning.pipe_interval()),
+        TimerMode::Repeating,
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn spawn_pipes(
     mut commands: Commands,
     sprites: Res<SpriteAssets>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    run_seed: Res<RunSeed>,
+    mut pipe_rng: ResMut<PipeRng>,
+    game_mode: Res<GameMode>,
+    levels: Res<Assets<Level>>,
+    tuning: Res<GameTuning>,
+    playfield: Res<Playfield>,
+) {
+    match &*game_mode {
+        GameMode::Endless => {
+            pipe_rng.0 = StdRng::seed_from_u64(run_seed.0);
+            for i in 0..tuning.pipe_amount {
+                let y_offset = generate_pipe_offset(&mut pipe_rng.0, &tuning, &playfield);
+                // Every run starts with no pipes passed
+                let motion = choose_pipe_motion(&mut pipe_rng.0, &tuning, 0);
+                let x_pos = WINDOW_MAX_X + (tuning.pipe_spacing_scaled() * i as f32);
+                spawn_pipe_pair(
+                    &mut commands,
+                    x_pos,
+                    y_offset,
+                    tuning.pipe_gap_size * SPRITE_SCALE,
+                    motion,
+                    &sprites.pipe_sprite,
+                );
+                spawn_gap_pickups(
+                    &mut commands,
+                    x_pos,
+                    y_offset,
+                    motion,
+                    &tuning,
+                    &mut pipe_rng.0,
+                    &sprites,
+                );
+            }
+        }
+        GameMode::Level(ActiveLevel { handle, .. }) => {
+            spawn_level_pipes(&mut commands, levels.get(handle), WINDOW_MAX_X, &sprites);
+        }
+        GameMode::Playtest {
+            level,
+            start_distance,
+        } => {
+            let start_x = WINDOW_MAX_X - start_distance * SPRITE_SCALE;
+            spawn_level_pipes(&mut commands, levels.get(level), start_x, &sprites);
+        }
+    }
+}
+
+fn spawn_level_pipes(
+    commands: &mut Commands,
+    level: Option<&Level>,
+    start_x: f32,
+    sprites: &SpriteAssets,
 ) {
-    for i in 0..PIPE_AMOUNT {
-        let y_offset = generate_pipe_offset(&mut thread_rng());
-        if let Ok(window) = window_query.get_single() {
-            let x_pos = window.width() / 2.0 + (PIPE_SPACING * SPRITE_SCALE * i as f32);
-            commands.spawn(PipeBundle::new(
-                Vec2::X * x_pos + Vec2::Y * (PIPE_VERTICAL_CENTER + y_offset),
-                PIPE_DIRECTION_UP,
-                &sprites.pipe_sprite.clone(),
-            ));
-            commands.spawn(PipeBundle::new(
-                Vec2::X * x_pos + Vec2::Y * (-PIPE_VERTICAL_CENTER + y_offset),
-                PIPE_DIRECTION_DOWN,
-                &sprites.pipe_sprite.clone(),
-            ));
+    let Some(level) = level else {
+        return;
+    };
+    let mut x_pos = start_x;
+    // Coins sit in every still gap and halfway between two still gaps in a row
+    let mut previous_still_gap: Option<Vec2> = None;
+    for level_pipe in level.pipes.iter() {
+        x_pos += level_pipe.distance * SPRITE_SCALE;
+        let gap = Vec2::new(x_pos, level_pipe.gap_y * SPRITE_SCALE);
+        let still_gap = level_pipe.motion.is_none().then_some(gap);
+        let previous_gap = std::mem::replace(&mut previous_still_gap, still_gap);
+        // Playtests can start part way along, so skip pairs that are already behind the player
+        if x_pos + PIPE_HALF_WIDTH_SCALED < WINDOW_MIN_X {
+            continue;
+        }
+        spawn_pipe_pair(
+            commands,
+            x_pos,
+            gap.y,
+            level_pipe.gap_size * SPRITE_SCALE,
+            level_pipe.motion,
+            &sprites.pipe_sprite,
+        );
+        if let Some(gap) = still_gap {
+            spawn_coin(commands, gap.x, gap.y, sprites);
+            if let Some(previous_gap) = previous_gap {
+                let halfway = previous_gap.midpoint(gap);
+                spawn_coin(commands, halfway.x, halfway.y, sprites);
+            }
         }
     }
+    commands.spawn(FinishLineBundle::new(
+        x_pos + FINISH_LINE_DISTANCE * SPRITE_SCALE,
+    ));
 }
 
-pub(crate) fn despawn_pipes(mut commands: Commands, pipe_query: Query<Entity, With<Pipe>>) {
+fn spawn_pipe_pair(
+    commands: &mut Commands,
+    x: f32,
+    gap_y: f32,
+    gap_size: f32,
+    motion: Option<PipeMotion>,
+    pipe_image: &Handle<Image>,
+) {
+    for direction in [PIPE_DIRECTION_UP, PIPE_DIRECTION_DOWN] {
+        let mut pipe = commands.spawn(PipeBundle::new(x, gap_y, gap_size, direction, pipe_image));
+        if let Some(motion) = motion {
+            pipe.insert(MovingPipe {
+                motion,
+                elapsed: 0.0,
+            });
+        }
+    }
+}
+
+/// Rolls a power-up or coins for an endless pair, leaving gaps that move empty
+fn spawn_gap_pickups(
+    commands: &mut Commands,
+    x: f32,
+    gap_y: f32,
+    motion: Option<PipeMotion>,
+    tuning: &GameTuning,
+    rand: &mut impl Rng,
+    sprites: &SpriteAssets,
+) {
+    let mut gap_is_empty = motion.is_none();
+    if gap_is_empty {
+        if let Some(kind) = choose_power_up(rand) {
+            spawn_power_up_pickup(commands, x, gap_y, kind, sprites);
+            gap_is_empty = false;
+        }
+    }
+    spawn_endless_coins(commands, x, gap_y, gap_is_empty, tuning, rand, sprites);
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) fn despawn_pipes(
+    mut commands: Commands,
+    pipe_query: Query<
+        Entity,
+        Or<(
+            With<Pipe>,
+            With<FinishLine>,
+            With<PowerUpPickup>,
+            With<Coin>,
+        )>,
+    >,
+) {
     for entity in pipe_query.iter() {
         commands.entity(entity).despawn();
     }
 }
 
-fn generate_pipe_offset(rand: &mut ThreadRng) -> f32 {
-    rand.gen_range(-PIPE_VERTICAL_OFFSET..PIPE_VERTICAL_OFFSET) * SPRITE_SCALE
+/// Gaps spread over a taller playfield in proportion to its height
+fn generate_pipe_offset(rand: &mut impl Rng, tuning: &GameTuning, playfield: &Playfield) -> f32 {
+    rand.gen_range(-tuning.pipe_vertical_offset..=tuning.pipe_vertical_offset)
+        * SPRITE_SCALE
+        * playfield.stretch()
+}
+
+/// Picks a motion for the next endless pair from the tiers unlocked after `pipes_passed`, if any
+///
+/// Tiers follow pipes passed rather than score so score multipliers can't change the layout a
+/// seed produces, and the chance is drawn on every call so the generator always advances alike
+fn choose_pipe_motion(
+    rand: &mut impl Rng,
+    tuning: &GameTuning,
+    pipes_passed: u32,
+) -> Option<PipeMotion> {
+    let moving = rand.gen_bool(tuning.moving_pipe_chance as f64);
+    let unlocked: Vec<&MovingPipeTier> = tuning
+        .moving_pipes
+        .iter()
+        .filter(|tier| tier.from_pipes <= pipes_passed)
+        .collect();
+    if unlocked.is_empty() || !moving {
+        return None;
+    }
+    Some(unlocked[rand.gen_range(0..unlocked.len())].motion)
 }
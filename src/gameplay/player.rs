@@ -1,11 +1,10 @@
 use super::*;
-use bevy::audio::Volume;
 use pipes::Pipe;
 use std::time::Duration;
 
 #[derive(Component)]
 pub(crate) struct Player {
-    velocity: f32,
+    pub(crate) velocity: f32,
 }
 
 #[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
@@ -15,8 +14,12 @@ pub(crate) enum PlayerState {
     WaitingToFall,
     Falling,
     Flapping,
+    Finished,
 }
 
+#[derive(Resource, Default)]
+pub(crate) struct Flaps(pub(crate) u32);
+
 #[derive(Bundle)]
 struct PlayerBundle {
     player: Player,
@@ -48,20 +51,14 @@ impl PlayerBundle {
 }
 
 impl FallDelayTimer {
-    fn new() -> FallDelayTimer {
-        FallDelayTimer(Timer::new(
-            Duration::from_secs_f32(FALL_SOUND_DELAY),
-            TimerMode::Once,
-        ))
+    fn new(delay: f32) -> FallDelayTimer {
+        FallDelayTimer(Timer::new(Duration::from_secs_f32(delay), TimerMode::Once))
     }
 }
 
 impl ResetDelayTimer {
-    fn new() -> ResetDelayTimer {
-        ResetDelayTimer(Timer::new(
-            Duration::from_secs_f32(FALL_RESET_DELAY),
-            TimerMode::Once,
-        ))
+    pub(crate) fn new(delay: f32) -> ResetDelayTimer {
+        ResetDelayTimer(Timer::new(Duration::from_secs_f32(delay), TimerMode::Once))
     }
 }
 
@@ -78,9 +75,10 @@ pub(crate) fn despawn_player(mut commands: Commands, player_query: Query<Entity,
 pub(crate) fn handle_auto_despawn(
     mut commands: Commands,
     player_query: Query<(Entity, &Transform), With<Player>>,
+    playfield: Res<Playfield>,
 ) {
     if let Ok((entity, player_transform)) = player_query.get_single() {
-        if player_transform.translation.y < WINDOW_MIN_Y - PLAYER_AUTO_DESPAWN_DISTANCE {
+        if player_transform.translation.y < playfield.min_y() - PLAYER_AUTO_DESPAWN_DISTANCE {
             commands.entity(entity).despawn();
         }
     }
@@ -88,140 +86,194 @@ pub(crate) fn handle_auto_despawn(
 
 pub(crate) fn update_player_transform(
     mut player_transform_query: Query<(&mut Player, &mut Transform)>,
+    tuning: Res<GameTuning>,
+    settings: Res<Settings>,
     time: Res<Time>,
 ) {
     if let Ok((mut player, mut player_transform)) = player_transform_query.get_single_mut() {
-        apply_player_gravity(&mut player, &mut player_transform, &time);
-        apply_player_rotation(&mut player, &mut player_transform);
+        apply_player_gravity(
+            &mut player,
+            &mut player_transform,
+            tuning.playing_gravity_strength,
+            &time,
+        );
+        apply_player_rotation(&mut player, &mut player_transform, &tuning, &settings);
     }
 }
 
 fn apply_player_gravity(
     player: &mut Mut<Player>,
     player_transform: &mut Mut<Transform>,
+    gravity_strength: f32,
     time: &Res<Time>,
 ) {
-    player.velocity -= time.delta_secs() * PLAYING_GRAVITY_STRENGTH;
+    player.velocity -= time.delta_secs() * gravity_strength;
     player_transform.translation.y += player.velocity * time.delta_secs();
 }
 
-fn apply_player_animation_gravity(
+fn apply_player_rotation(
     player: &mut Mut<Player>,
     player_transform: &mut Mut<Transform>,
-    time: &Res<Time>,
+    tuning: &GameTuning,
+    settings: &Settings,
 ) {
-    player.velocity -= time.delta_secs() * ANIMATION_GRAVITY_STRENGTH;
-    player_transform.translation.y += player.velocity * time.delta_secs();
+    player_transform.rotation = if settings.reduced_motion {
+        Quat::IDENTITY
+    } else {
+        velocity_rotation(player.velocity, tuning)
+    };
 }
 
-fn apply_player_rotation(player: &mut Mut<Player>, player_transform: &mut Mut<Transform>) {
-    player_transform.rotation = Quat::from_axis_angle(
+pub(crate) fn velocity_rotation(velocity: f32, tuning: &GameTuning) -> Quat {
+    Quat::from_axis_angle(
         Vec3::Z,
-        f32::clamp(player.velocity / VELOCITY_TO_ROTATION_RATIO, -90.0, 90.0).to_radians(),
-    );
+        f32::clamp(velocity / tuning.velocity_to_rotation_ratio, -90.0, 90.0).to_radians(),
+    )
 }
 
-pub(crate) fn handle_player_input(
-    mut commands: Commands,
-    mut player_query: Query<&mut Player>,
-    keys: Res<ButtonInput<KeyCode>>,
-    audio: Res<AudioAssets>,
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn handle_flaps(
+    mut player_query: Query<(&mut Player, &Transform)>,
+    mut flap_events: EventReader<FlapRequested>,
+    player_state: Res<State<PlayerState>>,
+    mut next_player_state: ResMut<NextState<PlayerState>>,
+    mut flaps: ResMut<Flaps>,
+    mut recorded_flaps: ResMut<RecordedFlaps>,
+    run_ticks: Res<RunTicks>,
+    mut play_sound_events: EventWriter<PlaySound>,
+    mut emit_particles_events: EventWriter<EmitParticles>,
+    tuning: Res<GameTuning>,
 ) {
-    if keys.just_pressed(FLAP_KEY) {
-        commands.spawn((
-            AudioPlayer::new(audio.flap_sound.clone()),
-            PlaybackSettings {
-                volume: Volume::new(FLAP_SOUND_VOLUME),
-                ..Default::default()
-            },
-        ));
-        if let Ok(mut player) = player_query.get_single_mut() {
-            player.velocity = PLAYER_FLAP_FORCE;
-        }
+    // Several sources may ask for a flap on the same tick, which is still a single flap
+    if flap_events.read().count() == 0 {
+        return;
+    }
+    match player_state.get() {
+        PlayerState::WaitingToStart => next_player_state.set(PlayerState::Flapping),
+        PlayerState::Flapping => (),
+        _ => return,
+    }
+    flaps.0 += 1;
+    recorded_flaps.0.push(run_ticks.0);
+    play_sound_events.send(PlaySound(SoundEffect::Flap));
+    if let Ok((mut player, transform)) = player_query.get_single_mut() {
+        player.velocity = tuning.player_flap_force;
+        emit_particles_events.send(EmitParticles {
+            burst: ParticleBurst::Flap,
+            position: transform.translation.truncate(),
+        });
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn handle_player_collision(
     mut commands: Commands,
-    player_transform_query: Query<&Transform, With<Player>>,
-    pipe_transform_query: Query<&Transform, With<Pipe>>,
-    mut player_query: Query<&mut Player>,
-    score_timer_query: Query<Entity, With<ScoreTimer>>,
-    audio: Res<AudioAssets>,
+    mut player_query: Query<(&mut Player, &Transform)>,
+    pipe_query: Query<(&Pipe, &Transform)>,
+    mut power_ups: ResMut<ActivePowerUps>,
+    mut run_ended_events: EventWriter<RunEnded>,
+    mut screen_impact_events: EventWriter<ScreenImpact>,
+    mut emit_particles_events: EventWriter<EmitParticles>,
+    run_totals: RunTotals,
+    mut play_sound_events: EventWriter<PlaySound>,
+    tuning: Res<GameTuning>,
+    assists: Res<RunAssists>,
+    playfield: Res<Playfield>,
+    mut run_over: ResMut<RunOver>,
     mut next_player_state: ResMut<NextState<PlayerState>>,
 ) {
-    if let Ok(player_transform) = player_transform_query.get_single() {
-        let pipe_collision = player_pipe_collision(&player_transform, pipe_transform_query);
-        let screen_collision = player_screen_collision(&player_transform);
+    if run_over.0 {
+        return;
+    }
+    if let Ok((mut player, player_transform)) = player_query.get_single_mut() {
+        let mut pipe_collision = player_pipe_collision(
+            player_transform,
+            tuning.player_collision_radius() * power_ups.player_scale() * assists.collision_scale,
+            pipe_query,
+        );
+        let screen_collision = player_screen_collision(player_transform, &playfield);
+        // The shield only protects against pipes, the edges of the screen are still deadly
+        if pipe_collision.is_some() && screen_collision.is_none() && power_ups.absorb_hit() {
+            pipe_collision = None;
+        }
 
-        if pipe_collision || screen_collision {
-            if let Ok(score_display) = score_timer_query.get_single() {
-                commands.entity(score_display).despawn();
-            }
-            if let Ok(mut player) = player_query.get_single_mut() {
-                player.velocity = 0.0;
-            }
-            commands.spawn((
-                AudioPlayer::new(audio.smack_sound.clone()),
-                PlaybackSettings {
-                    volume: Volume::new(SMACK_SOUND_VOLUME),
-                    ..Default::default()
-                },
-            ));
+        if let Some(cause) = screen_collision.or(pipe_collision) {
+            run_over.0 = true;
+            player.velocity = 0.0;
+            play_sound_events.send(PlaySound(SoundEffect::Smack));
+            screen_impact_events.send(ScreenImpact(CRASH_IMPACT));
+            emit_particles_events.send(EmitParticles {
+                burst: ParticleBurst::Crash,
+                position: player_transform.translation.truncate(),
+            });
             next_player_state.set(PlayerState::WaitingToFall);
-            commands.spawn(FallDelayTimer::new());
+            commands.spawn(FallDelayTimer::new(tuning.fall_sound_delay));
+            run_ended_events.send(run_totals.run_ended(
+                player_transform.translation.y,
+                tuning.pipe_speed,
+                Some(cause),
+            ));
         }
     }
 }
 
 fn player_pipe_collision(
     player_transform: &Transform,
-    pipe_transform_query: Query<&Transform, With<Pipe>>,
-) -> bool {
-    for pipe_transform in pipe_transform_query.iter() {
-        let too_far_right = pipe_transform.translation.x - PIPE_HALF_WIDTH_SCALED
-            >= PLAYER_COLLISION_RADIUS_FACTORED;
-        let too_far_left = pipe_transform.translation.x + PIPE_HALF_WIDTH_SCALED
-            <= PLAYER_COLLISION_RADIUS_FACTORED;
+    player_collision_radius: f32,
+    pipe_query: Query<(&Pipe, &Transform)>,
+) -> Option<DeathCause> {
+    for (pipe, pipe_transform) in pipe_query.iter() {
+        let too_far_right =
+            pipe_transform.translation.x - PIPE_HALF_WIDTH_SCALED >= player_collision_radius;
+        let too_far_left =
+            pipe_transform.translation.x + PIPE_HALF_WIDTH_SCALED <= player_collision_radius;
         if too_far_right || too_far_left {
             continue;
         }
 
         if circle_rectangle_collision(
             player_transform,
-            PLAYER_COLLISION_RADIUS_FACTORED,
+            player_collision_radius,
             pipe_transform,
             PIPE_WIDTH_SCALED,
             PIPE_HEIGHT_SCALED,
         ) {
-            return true;
+            return Some(if pipe.is_top() {
+                DeathCause::TopPipe
+            } else {
+                DeathCause::BottomPipe
+            });
         }
     }
-    false
+    None
 }
 
-fn player_screen_collision(player_transform: &Transform) -> bool {
-    player_transform.translation.y <= WINDOW_MIN_Y || player_transform.translation.y >= WINDOW_MAX_Y
+fn player_screen_collision(
+    player_transform: &Transform,
+    playfield: &Playfield,
+) -> Option<DeathCause> {
+    if player_transform.translation.y <= playfield.min_y() {
+        Some(DeathCause::Floor)
+    } else if player_transform.translation.y >= playfield.max_y() {
+        Some(DeathCause::Ceiling)
+    } else {
+        None
+    }
 }
 
 pub(crate) fn handle_fall_sound_delay_timer(
     mut commands: Commands,
     mut query: Query<(Entity, &mut FallDelayTimer)>,
-    audio: Res<AudioAssets>,
+    mut play_sound_events: EventWriter<PlaySound>,
+    tuning: Res<GameTuning>,
     time: Res<Time>,
     mut next_player_state: ResMut<NextState<PlayerState>>,
 ) {
     if let Ok((entity, mut delay_timer)) = query.get_single_mut() {
         if delay_timer.0.tick(time.delta()).just_finished() {
             next_player_state.set(PlayerState::Falling);
-            commands.spawn(ResetDelayTimer::new());
-            commands.spawn((
-                AudioPlayer::new(audio.fall_sound.clone()),
-                PlaybackSettings {
-                    volume: Volume::new(FALL_SOUND_VOLUME),
-                    ..Default::default()
-                },
-            ));
+            commands.spawn(ResetDelayTimer::new(tuning.fall_reset_delay));
+            play_sound_events.send(PlaySound(SoundEffect::Fall));
             commands.entity(entity).despawn();
         }
     }
@@ -231,48 +283,50 @@ pub(crate) fn handle_fall_reset_delay_timer(
     mut commands: Commands,
     mut query: Query<(Entity, &mut ResetDelayTimer)>,
     time: Res<Time>,
-    mut next_app_state: ResMut<NextState<AppState>>,
+    mut transition_events: EventWriter<StartTransition>,
 ) {
     if let Ok((entity, mut delay_timer)) = query.get_single_mut() {
         if delay_timer.0.tick(time.delta()).just_finished() {
             commands.entity(entity).despawn();
-            next_app_state.set(AppState::RetryMenu);
-        }
-    }
-}
-
-pub(crate) fn handle_frozen_toggle(
-    mut commands: Commands,
-    mut player_query: Query<&mut Player, Without<Pipe>>,
-    player_state: Res<State<PlayerState>>,
-    mut next_player_state: ResMut<NextState<PlayerState>>,
-    keys: Res<ButtonInput<KeyCode>>,
-    audio: Res<AudioAssets>,
-) {
-    if keys.just_pressed(FLAP_KEY) {
-        if let PlayerState::WaitingToStart = player_state.get() {
-            commands.spawn(ScoreTimer::new());
-            next_player_state.set(PlayerState::Flapping);
-            commands.spawn((
-                AudioPlayer::new(audio.flap_sound.clone()),
-                PlaybackSettings {
-                    volume: Volume::new(FLAP_SOUND_VOLUME),
-                    ..Default::default()
-                },
-            ));
-            if let Ok(mut player) = player_query.get_single_mut() {
-                player.velocity = PLAYER_FLAP_FORCE;
-            }
+            transition_events.send(StartTransition {
+                to: AppState::RetryMenu,
+                style: TransitionStyle::Fade,
+            });
         }
     }
 }
 
 pub(crate) fn handle_fall_animation(
     mut player_transform_query: Query<(&mut Player, &mut Transform)>,
+    tuning: Res<GameTuning>,
+    settings: Res<Settings>,
     time: Res<Time>,
 ) {
     if let Ok((mut player, mut player_transform)) = player_transform_query.get_single_mut() {
-        apply_player_animation_gravity(&mut player, &mut player_transform, &time);
-        apply_player_rotation(&mut player, &mut player_transform);
+        apply_player_gravity(
+            &mut player,
+            &mut player_transform,
+            tuning.animation_gravity_strength,
+            &time,
+        );
+        apply_player_rotation(&mut player, &mut player_transform, &tuning, &settings);
     }
 }
+
+pub(crate) fn reset_run_state(
+    mut flaps: ResMut<Flaps>,
+    mut outcome: ResMut<LevelOutcome>,
+    mut run_clock: ResMut<RunClock>,
+    mut pipes_passed: ResMut<PipesPassed>,
+    mut run_ticks: ResMut<RunTicks>,
+    mut recorded_flaps: ResMut<RecordedFlaps>,
+    mut run_over: ResMut<RunOver>,
+) {
+    flaps.0 = 0;
+    outcome.stars = None;
+    run_clock.0 = 0.0;
+    pipes_passed.0 = 0;
+    run_ticks.0 = 0;
+    recorded_flaps.0.clear();
+    run_over.0 = false;
+}
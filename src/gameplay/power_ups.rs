@@ -0,0 +1,183 @@
+use super::*;
+use bevy::utils::HashMap;
+use rand::Rng;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum PowerUpKind {
+    Shield,
+    SlowMotion,
+    Shrink,
+    DoubleScore,
+}
+
+#[derive(Component)]
+pub(crate) struct PowerUpPickup(PowerUpKind);
+
+/// Power-ups collected this run and the seconds each one has left
+#[derive(Resource, Default)]
+pub(crate) struct ActivePowerUps {
+    remaining: HashMap<PowerUpKind, f32>,
+    /// Seconds left to fly clear of the pipe that broke the shield
+    shield_grace: f32,
+}
+
+impl PowerUpKind {
+    pub(crate) const ALL: [PowerUpKind; 4] = [
+        PowerUpKind::Shield,
+        PowerUpKind::SlowMotion,
+        PowerUpKind::Shrink,
+        PowerUpKind::DoubleScore,
+    ];
+
+    fn duration(self) -> f32 {
+        match self {
+            PowerUpKind::Shield => SHIELD_DURATION,
+            PowerUpKind::SlowMotion => SLOW_MOTION_DURATION,
+            PowerUpKind::Shrink => SHRINK_DURATION,
+            PowerUpKind::DoubleScore => DOUBLE_SCORE_DURATION,
+        }
+    }
+}
+
+impl ActivePowerUps {
+    pub(crate) fn remaining(&self, kind: PowerUpKind) -> Option<f32> {
+        self.remaining.get(&kind).copied()
+    }
+
+    fn is_active(&self, kind: PowerUpKind) -> bool {
+        self.remaining.contains_key(&kind)
+    }
+
+    pub(crate) fn score_multiplier(&self) -> u32 {
+        if self.is_active(PowerUpKind::DoubleScore) {
+            2
+        } else {
+            1
+        }
+    }
+
+    pub(crate) fn player_scale(&self) -> f32 {
+        if self.is_active(PowerUpKind::Shrink) {
+            SHRINK_FACTOR
+        } else {
+            1.0
+        }
+    }
+
+    /// Uses up the shield to ignore a pipe collision, returning whether the hit was absorbed
+    pub(crate) fn absorb_hit(&mut self) -> bool {
+        if self.shield_grace > 0.0 {
+            return true;
+        }
+        if self.remaining.remove(&PowerUpKind::Shield).is_some() {
+            self.shield_grace = SHIELD_GRACE_DURATION;
+            return true;
+        }
+        false
+    }
+}
+
+/// Rolls whether a new pipe gap holds a power-up and which one
+pub(crate) fn choose_power_up(rand: &mut impl Rng) -> Option<PowerUpKind> {
+    if !rand.gen_bool(POWER_UP_CHANCE) {
+        return None;
+    }
+    Some(PowerUpKind::ALL[rand.gen_range(0..PowerUpKind::ALL.len())])
+}
+
+pub(crate) fn spawn_power_up_pickup(
+    commands: &mut Commands,
+    x: f32,
+    y: f32,
+    kind: PowerUpKind,
+    sprites: &SpriteAssets,
+) {
+    commands.spawn((
+        PowerUpPickup(kind),
+        Sprite {
+            image: sprites.power_up_sprite(kind).clone(),
+            ..Default::default()
+        },
+        Transform::from_xyz(x, y, PICKUP_SPRITE_Z).with_scale(Vec3::new(
+            SPRITE_SCALE,
+            SPRITE_SCALE,
+            1.0,
+        )),
+    ));
+}
+
+pub(crate) fn handle_power_up_pickups(
+    mut commands: Commands,
+    player_query: Query<&Transform, With<Player>>,
+    pickup_query: Query<(Entity, &PowerUpPickup, &Transform)>,
+    mut power_ups: ResMut<ActivePowerUps>,
+    mut play_sound_events: EventWriter<PlaySound>,
+    tuning: Res<GameTuning>,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    let player_collision_radius = tuning.player_collision_radius() * power_ups.player_scale();
+    for (entity, pickup, pickup_transform) in pickup_query.iter() {
+        if !circle_rectangle_collision(
+            player_transform,
+            player_collision_radius,
+            pickup_transform,
+            POWER_UP_SIZE_SCALED,
+            POWER_UP_SIZE_SCALED,
+        ) {
+            continue;
+        }
+        // Collecting a power-up again restarts its timer
+        power_ups.remaining.insert(pickup.0, pickup.0.duration());
+        commands.entity(entity).despawn();
+        play_sound_events.send(PlaySound(SoundEffect::PowerUp));
+    }
+}
+
+// Timers run on fixed time so hit-stop, the speed assist and replays all see the same timings,
+// with slow motion divided back out so it does not stretch its own duration
+pub(crate) fn tick_power_ups(mut power_ups: ResMut<ActivePowerUps>, time: Res<Time>) {
+    let mut delta = time.delta_secs();
+    if power_ups.is_active(PowerUpKind::SlowMotion) {
+        delta /= SLOW_MOTION_SPEED;
+    }
+    power_ups.shield_grace = (power_ups.shield_grace - delta).max(0.0);
+    power_ups.remaining.retain(|_, remaining| {
+        *remaining -= delta;
+        *remaining > 0.0
+    });
+}
+
+/// Slows the run down for slow motion and the speed assist
+pub(crate) fn apply_game_speed(
+    power_ups: Res<ActivePowerUps>,
+    player_state: Res<State<PlayerState>>,
+    assists: Res<RunAssists>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    let flapping = *player_state.get() == PlayerState::Flapping;
+    let slowed = power_ups.is_active(PowerUpKind::SlowMotion) && flapping;
+    let mut speed = if slowed { SLOW_MOTION_SPEED } else { 1.0 };
+    // Virtual time drives the fixed timestep, so the assist slows the whole simulation down
+    if flapping {
+        speed *= assists.game_speed;
+    }
+    if time.relative_speed() != speed {
+        time.set_relative_speed(speed);
+    }
+}
+
+pub(crate) fn update_player_scale(
+    mut player_query: Query<&mut Transform, With<Player>>,
+    power_ups: Res<ActivePowerUps>,
+) {
+    if let Ok(mut player_transform) = player_query.get_single_mut() {
+        let scale = SPRITE_SCALE * power_ups.player_scale();
+        player_transform.scale = Vec3::new(scale, scale, 1.0);
+    }
+}
+
+pub(crate) fn reset_power_ups(mut power_ups: ResMut<ActivePowerUps>) {
+    *power_ups = ActivePowerUps::default();
+}
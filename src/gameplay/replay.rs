@@ -0,0 +1,88 @@
+use super::*;
+use serde::{Deserialize, Serialize};
+
+/// Everything needed to fly a run again, saved after every run and read back with `--replay`
+#[derive(Resource, Serialize, Deserialize, Clone, Default)]
+pub(crate) struct Replay {
+    pub(crate) seed: u64,
+    pub(crate) mode: LaunchMode,
+    /// The [`RunTicks`] at which each flap happened
+    pub(crate) flaps: Vec<u32>,
+    /// The playfield the run was flown on, which decides where gaps can be and where the edges are
+    #[serde(default)]
+    pub(crate) playfield: Playfield,
+}
+
+impl Saved for Replay {
+    const KEY: &'static str = "last_replay";
+}
+
+/// Flaps of the current run, kept for its replay
+#[derive(Resource, Default)]
+pub(crate) struct RecordedFlaps(pub(crate) Vec<u32>);
+
+/// A replay being flown, present only when the game was started with `--replay`
+#[derive(Resource)]
+pub(crate) struct ReplayPlayback {
+    pub(crate) replay: Replay,
+    next_flap: usize,
+}
+
+impl ReplayPlayback {
+    pub(crate) fn new(replay: Replay) -> ReplayPlayback {
+        ReplayPlayback {
+            replay,
+            next_flap: 0,
+        }
+    }
+}
+
+pub(crate) fn play_replay_flaps(
+    mut playback: ResMut<ReplayPlayback>,
+    run_ticks: Res<RunTicks>,
+    mut flap_events: EventWriter<FlapRequested>,
+) {
+    let ReplayPlayback { replay, next_flap } = playback.as_mut();
+    while replay
+        .flaps
+        .get(*next_flap)
+        .is_some_and(|tick| *tick <= run_ticks.0)
+    {
+        flap_events.send(FlapRequested);
+        *next_flap += 1;
+    }
+}
+
+pub(crate) fn rewind_replay(playback: Option<ResMut<ReplayPlayback>>) {
+    if let Some(mut playback) = playback {
+        playback.next_flap = 0;
+    }
+}
+
+// Races depend on other players and playtests on unsaved levels, so neither can be replayed
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn save_replay(
+    mut run_ended_events: EventReader<RunEnded>,
+    mut replay: ResMut<Replay>,
+    recorded_flaps: Res<RecordedFlaps>,
+    run_seed: Res<RunSeed>,
+    game_mode: Res<GameMode>,
+    playfield: Res<Playfield>,
+    race_session: Option<Res<RaceSession>>,
+    playback: Option<Res<ReplayPlayback>>,
+) {
+    if run_ended_events.read().count() == 0 || race_session.is_some() || playback.is_some() {
+        return;
+    }
+    let mode = match game_mode.as_ref() {
+        GameMode::Endless => LaunchMode::Endless,
+        GameMode::Level(level) => LaunchMode::Level(level.key.clone()),
+        GameMode::Playtest { .. } => return,
+    };
+    *replay = Replay {
+        seed: run_seed.0,
+        mode,
+        flaps: recorded_flaps.0.clone(),
+        playfield: *playfield,
+    };
+}
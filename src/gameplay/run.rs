@@ -0,0 +1,87 @@
+use super::*;
+use bevy::ecs::system::SystemParam;
+use serde::{Deserialize, Serialize};
+
+/// Seconds of flapping in the current run
+#[derive(Resource, Default)]
+pub(crate) struct RunClock(pub(crate) f32);
+
+/// Fixed updates since the current run started, which replays use to time flaps
+#[derive(Resource, Default)]
+pub(crate) struct RunTicks(pub(crate) u32);
+
+/// Pipe pairs flown through in the current run, which differs from the score while double score is active
+#[derive(Resource, Default)]
+pub(crate) struct PipesPassed(pub(crate) u32);
+
+/// Set once the current run has ended, since a frame can run several fixed updates before the player state changes
+#[derive(Resource, Default)]
+pub(crate) struct RunOver(pub(crate) bool);
+
+/// What the bird hit to end a run
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub(crate) enum DeathCause {
+    TopPipe,
+    BottomPipe,
+    Ceiling,
+    Floor,
+}
+
+/// Sent once when a run is over, either by crashing or by crossing a level's finish line
+#[derive(Event)]
+pub(crate) struct RunEnded {
+    pub(crate) score: u32,
+    pub(crate) pipes: u32,
+    pub(crate) flaps: u32,
+    pub(crate) duration: f32,
+    /// Distance flown along the course and height of the bird when the run ended
+    pub(crate) position: Vec2,
+    /// `None` when the run ended at a finish line
+    pub(crate) cause: Option<DeathCause>,
+}
+
+/// The totals of the run in progress, which are reported when it ends
+#[derive(SystemParam)]
+pub(crate) struct RunTotals<'w> {
+    pub(crate) score: Res<'w, Score>,
+    pub(crate) pipes_passed: Res<'w, PipesPassed>,
+    pub(crate) flaps: Res<'w, Flaps>,
+    pub(crate) run_clock: Res<'w, RunClock>,
+}
+
+impl RunTotals<'_> {
+    /// The end of the run with the bird at `height`, placed along the course by how long it has been flying
+    pub(crate) fn run_ended(
+        &self,
+        height: f32,
+        pipe_speed: f32,
+        cause: Option<DeathCause>,
+    ) -> RunEnded {
+        RunEnded {
+            score: self.score.0,
+            pipes: self.pipes_passed.0,
+            flaps: self.flaps.0,
+            duration: self.run_clock.0,
+            position: Vec2::new(self.run_clock.0 * pipe_speed, height),
+            cause,
+        }
+    }
+}
+
+impl DeathCause {
+    pub(crate) const ALL: [DeathCause; 4] = [
+        DeathCause::TopPipe,
+        DeathCause::BottomPipe,
+        DeathCause::Ceiling,
+        DeathCause::Floor,
+    ];
+}
+
+pub(crate) fn tick_run_clock(
+    mut run_clock: ResMut<RunClock>,
+    mut run_ticks: ResMut<RunTicks>,
+    time: Res<Time>,
+) {
+    run_clock.0 += time.delta_secs();
+    run_ticks.0 += 1;
+}
@@ -0,0 +1,23 @@
+use super::*;
+use rand::rngs::StdRng;
+use rand::{thread_rng, Rng, SeedableRng};
+
+#[derive(Resource, Default)]
+pub(crate) struct RunSeed(pub(crate) u64);
+
+/// A seed from the command line or a replay, used for every run instead of a random one
+#[derive(Resource, Default)]
+pub(crate) struct FixedSeed(pub(crate) Option<u64>);
+
+#[derive(Resource)]
+pub(crate) struct PipeRng(pub(crate) StdRng);
+
+impl Default for PipeRng {
+    fn default() -> PipeRng {
+        PipeRng(StdRng::seed_from_u64(0))
+    }
+}
+
+pub(crate) fn roll_run_seed(mut run_seed: ResMut<RunSeed>, fixed_seed: Res<FixedSeed>) {
+    run_seed.0 = fixed_seed.0.unwrap_or_else(|| thread_rng().gen());
+}
@@ -0,0 +1,63 @@
+use super::*;
+use serde::{Deserialize, Serialize};
+
+/// Skins bought in the Birds menu and the one currently worn, by [`Skin::key`]
+#[derive(Resource, Serialize, Deserialize)]
+pub(crate) struct SkinCollection {
+    pub(crate) unlocked: Vec<String>,
+    pub(crate) equipped: String,
+}
+
+impl Default for SkinCollection {
+    fn default() -> SkinCollection {
+        SkinCollection {
+            unlocked: Vec::new(),
+            equipped: DEFAULT_SKIN.to_string(),
+        }
+    }
+}
+
+impl Saved for SkinCollection {
+    const KEY: &'static str = "skins";
+}
+
+impl SkinCollection {
+    pub(crate) fn is_unlocked(&self, skin: &Skin) -> bool {
+        skin.price == 0 || self.unlocked.iter().any(|key| key == skin.key)
+    }
+
+    pub(crate) fn is_equipped(&self, skin: &Skin) -> bool {
+        self.equipped == skin.key
+    }
+
+    /// Equips the skin, paying for it from the wallet first if it is still locked.
+    /// Does nothing when the wallet cannot cover the price.
+    pub(crate) fn buy_or_equip(&mut self, skin: &Skin, wallet: &mut Wallet) {
+        if !self.is_unlocked(skin) {
+            if wallet.coins < skin.price {
+                return;
+            }
+            wallet.coins -= skin.price;
+            self.unlocked.push(skin.key.to_string());
+        }
+        self.equipped = skin.key.to_string();
+    }
+
+    // Saves can name skins that no longer exist, which fall back to the first one
+    fn equipped_index(&self) -> usize {
+        SKINS
+            .iter()
+            .position(|skin| skin.key == self.equipped)
+            .unwrap_or_default()
+    }
+}
+
+pub(crate) fn apply_equipped_skin(
+    skins: Res<SkinCollection>,
+    skin_assets: Res<SkinAssets>,
+    mut sprites: ResMut<SpriteAssets>,
+) {
+    let index = skins.equipped_index();
+    sprites.player_sprite = skin_assets.bird_sprites[index].clone();
+    sprites.pipe_sprite = skin_assets.pipe_sprites[index].clone();
+}
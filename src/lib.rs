@@ -1,12 +1,31 @@
+mod achievements;
 mod app;
 mod assets;
+mod audio;
+mod camera_effects;
+mod editor;
 mod gameplay;
+mod net;
+mod particles;
+mod save;
+mod settings;
+mod stats;
 mod ui;
 
-pub use app::AppPlugin;
+pub use app::{AppPlugin, LaunchError, LaunchMode, LaunchOptions};
+pub use stats::{export_saved_run_history, HistoryExportError};
 
+use achievements::*;
 use app::*;
 use assets::*;
+use audio::*;
 use bevy::prelude::*;
+use camera_effects::*;
+use editor::*;
 use gameplay::*;
+use net::*;
+use particles::*;
+use save::*;
+use settings::*;
+use stats::*;
 use ui::*;
@@ -1,16 +1,142 @@
 use bevy::app::App;
 use bevy::diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin};
-use flappy_bird;
+use flappy_bird::{AppPlugin, LaunchOptions};
+use std::process::ExitCode;
 use std::time::Duration;
 
-fn main() {
-    App::new()
-        .add_plugins(flappy_bird::AppPlugin)
-        .add_plugins(FrameTimeDiagnosticsPlugin)
-        .add_plugins(LogDiagnosticsPlugin {
+#[cfg(not(target_arch = "wasm32"))]
+mod cli {
+    use clap::error::ErrorKind;
+    use clap::{ArgGroup, CommandFactory, Parser};
+    use flappy_bird::{LaunchMode, LaunchOptions};
+    use std::path::PathBuf;
+
+    /// An ECS Flappy Bird clone
+    #[derive(Parser)]
+    #[command(version, group(ArgGroup::new("window").args(["windowed", "fullscreen"])))]
+    pub(crate) struct Cli {
+        /// Seed for every run instead of a random one
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Start straight into a mode: endless, editor or level:<name>
+        #[arg(long)]
+        mode: Option<LaunchMode>,
+        /// Run in a window
+        #[arg(long)]
+        windowed: bool,
+        /// Run in borderless fullscreen
+        #[arg(long)]
+        fullscreen: bool,
+        /// Window width in pixels
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+        width: Option<u32>,
+        /// Window height in pixels
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+        height: Option<u32>,
+        /// Start with all sound turned off
+        #[arg(long)]
+        mute: bool,
+        /// Do not print the frames per second every second
+        #[arg(long)]
+        no_fps_log: bool,
+        /// Let the game fly the bird on its own
+        #[arg(long, conflicts_with = "replay")]
+        autoplay: bool,
+        /// Fly a saved replay, such as last_replay.ron from the save folder
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["seed", "mode"])]
+        replay: Option<PathBuf>,
+        /// Read these options from a RON file, flags given on the command line take precedence
+        #[arg(long, value_name = "FILE")]
+        config: Option<PathBuf>,
+        /// Write the run history to a .csv or .json file and exit without starting the game
+        #[arg(long, value_name = "FILE")]
+        export_history: Option<PathBuf>,
+    }
+
+    impl Cli {
+        pub(crate) fn export_history(&self) -> Option<&PathBuf> {
+            self.export_history.as_ref()
+        }
+
+        /// Combines the flags with the config file, exiting with an error if the file is unusable
+        pub(crate) fn into_options(self) -> LaunchOptions {
+            let mut options = match &self.config {
+                Some(path) => LaunchOptions::from_file(path)
+                    .unwrap_or_else(|error| Cli::command().error(ErrorKind::Io, error).exit()),
+                None => LaunchOptions::default(),
+            };
+            if self.replay.is_some() {
+                options.seed = None;
+                options.mode = None;
+            }
+            options.seed = self.seed.or(options.seed);
+            options.mode = self.mode.or(options.mode);
+            if self.windowed || self.fullscreen {
+                options.fullscreen = Some(self.fullscreen);
+            }
+            options.width = self.width.or(options.width);
+            options.height = self.height.or(options.height);
+            options.mute |= self.mute;
+            options.no_fps_log |= self.no_fps_log;
+            options.autoplay |= self.autoplay;
+            options.replay = self.replay.or(options.replay);
+            options
+                .validate()
+                .unwrap_or_else(|error| invalid_options(error));
+            options
+        }
+    }
+
+    pub(crate) fn invalid_options(error: impl std::fmt::Display) -> ! {
+        Cli::command().error(ErrorKind::InvalidValue, error).exit()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() -> ExitCode {
+    use clap::Parser;
+
+    let cli = cli::Cli::parse();
+    if let Some(path) = cli.export_history() {
+        return match flappy_bird::export_saved_run_history(path) {
+            Ok(()) => {
+                println!("Exported run history to {}", path.display());
+                ExitCode::SUCCESS
+            }
+            Err(error) => {
+                eprintln!("error: {error}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+    run(cli.into_options())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn main() -> ExitCode {
+    run(LaunchOptions::default())
+}
+
+fn run(options: LaunchOptions) -> ExitCode {
+    let no_fps_log = options.no_fps_log;
+    let app_plugin = match AppPlugin::new(options) {
+        Ok(app_plugin) => app_plugin,
+        #[cfg(not(target_arch = "wasm32"))]
+        Err(error) => cli::invalid_options(error),
+        #[cfg(target_arch = "wasm32")]
+        Err(_) => unreachable!("the default launch options are always valid"),
+    };
+
+    let mut app = App::new();
+    app.add_plugins(app_plugin)
+        .add_plugins(FrameTimeDiagnosticsPlugin);
+    if !no_fps_log {
+        app.add_plugins(LogDiagnosticsPlugin {
             wait_duration: Duration::from_secs(1),
             filter: Some(vec![FrameTimeDiagnosticsPlugin::FPS]),
             ..Default::default()
-        })
-        .run();
+        });
+    }
+    app.run();
+    ExitCode::SUCCESS
 }
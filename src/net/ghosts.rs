@@ -0,0 +1,155 @@
+use super::*;
+use std::collections::{HashMap, VecDeque};
+
+#[derive(Component)]
+pub(crate) struct Ghost {
+    player_id: u8,
+}
+
+#[derive(Resource, Default)]
+pub(crate) struct RaceDistance(pub(crate) f32);
+
+struct GhostSnapshot {
+    received: f32,
+    state: RacerState,
+}
+
+#[derive(Resource, Default)]
+pub(crate) struct GhostSnapshots(HashMap<u8, VecDeque<GhostSnapshot>>);
+
+#[derive(Bundle)]
+struct GhostBundle {
+    ghost: Ghost,
+    sprite: Sprite,
+    transform: Transform,
+}
+
+impl GhostBundle {
+    fn new(player_id: u8, player_sprite: &Handle<Image>) -> GhostBundle {
+        GhostBundle {
+            sprite: Sprite {
+                image: player_sprite.clone(),
+                color: Color::WHITE.with_alpha(GHOST_ALPHA),
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(0.0, 0.0, GHOST_SPRITE_Z).with_scale(Vec3::new(
+                SPRITE_SCALE,
+                SPRITE_SCALE,
+                1.0,
+            )),
+            ghost: Ghost { player_id },
+        }
+    }
+}
+
+impl GhostSnapshots {
+    pub(crate) fn push(&mut self, state: RacerState, received: f32) {
+        let snapshots = self.0.entry(state.player_id).or_default();
+        snapshots.push_back(GhostSnapshot { received, state });
+        if snapshots.len() > GHOST_SNAPSHOT_LIMIT {
+            snapshots.pop_front();
+        }
+    }
+
+    pub(crate) fn remove(&mut self, player_id: u8) {
+        self.0.remove(&player_id);
+    }
+
+    // Samples a racer slightly in the past so there is usually a snapshot on both sides
+    fn sample(&self, player_id: u8, now: f32) -> Option<RacerState> {
+        let snapshots = self.0.get(&player_id)?;
+        let render_time = now - GHOST_INTERPOLATION_DELAY;
+        let next_index = snapshots
+            .iter()
+            .position(|snapshot| snapshot.received >= render_time);
+
+        match next_index {
+            None => snapshots.back().map(|snapshot| snapshot.state),
+            Some(0) => snapshots.front().map(|snapshot| snapshot.state),
+            Some(index) => {
+                let previous = &snapshots[index - 1];
+                let next = &snapshots[index];
+                let span = (next.received - previous.received).max(f32::EPSILON);
+                let t = ((render_time - previous.received) / span).clamp(0.0, 1.0);
+                Some(RacerState {
+                    distance: previous.state.distance.lerp(next.state.distance, t),
+                    y: previous.state.y.lerp(next.state.y, t),
+                    velocity: previous.state.velocity.lerp(next.state.velocity, t),
+                    ..next.state
+                })
+            }
+        }
+    }
+
+    fn last_received(&self, player_id: u8) -> Option<f32> {
+        self.0
+            .get(&player_id)?
+            .back()
+            .map(|snapshot| snapshot.received)
+    }
+}
+
+pub(crate) fn reset_race_tracking(
+    mut distance: ResMut<RaceDistance>,
+    mut snapshots: ResMut<GhostSnapshots>,
+) {
+    distance.0 = 0.0;
+    snapshots.0.clear();
+}
+
+pub(crate) fn track_race_distance(
+    mut distance: ResMut<RaceDistance>,
+    tuning: Res<GameTuning>,
+    time: Res<Time>,
+) {
+    distance.0 += time.delta_secs() * tuning.pipe_speed;
+}
+
+pub(crate) fn sync_ghosts(
+    mut commands: Commands,
+    mut ghost_query: Query<(Entity, &Ghost, &mut Sprite, &mut Transform)>,
+    snapshots: Res<GhostSnapshots>,
+    distance: Res<RaceDistance>,
+    sprites: Res<SpriteAssets>,
+    tuning: Res<GameTuning>,
+    time: Res<Time<Real>>,
+) {
+    let now = time.elapsed_secs();
+
+    for (entity, ghost, mut sprite, mut transform) in ghost_query.iter_mut() {
+        let stale = snapshots
+            .last_received(ghost.player_id)
+            .is_none_or(|received| now - received > PEER_TIMEOUT);
+        let Some(state) = snapshots.sample(ghost.player_id, now).filter(|_| !stale) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+
+        transform.translation.x = state.distance - distance.0;
+        transform.translation.y = state.y;
+        transform.rotation = velocity_rotation(state.velocity, &tuning);
+        sprite.color = Color::WHITE.with_alpha(if state.alive {
+            GHOST_ALPHA
+        } else {
+            GHOST_DEAD_ALPHA
+        });
+    }
+
+    for (&player_id, player_snapshots) in snapshots.0.iter() {
+        let fresh = player_snapshots
+            .back()
+            .is_some_and(|snapshot| now - snapshot.received <= PEER_TIMEOUT);
+        let spawned = ghost_query
+            .iter()
+            .any(|(_, ghost, _, _)| ghost.player_id == player_id);
+        if fresh && !spawned {
+            commands.spawn(GhostBundle::new(player_id, &sprites.player_sprite));
+        }
+    }
+}
+
+pub(crate) fn despawn_ghosts(mut commands: Commands, ghost_query: Query<Entity, With<Ghost>>) {
+    for entity in ghost_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
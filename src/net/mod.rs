@@ -0,0 +1,59 @@
+use super::*;
+
+mod ghosts;
+mod protocol;
+mod session;
+
+// Re-exports
+pub(crate) use ghosts::*;
+pub(crate) use protocol::*;
+pub(crate) use session::*;
+
+// Connection
+const RACE_PORT: u16 = 7878;
+const PACKET_MAGIC: [u8; 2] = *b"FB";
+const MAX_PACKET_SIZE: usize = 64;
+const HOST_PLAYER_ID: u8 = 0;
+const MAX_RACERS: usize = 8;
+
+const HEARTBEAT_INTERVAL: f32 = 0.5;
+const STATE_SEND_INTERVAL: f32 = 1.0 / 20.0;
+const PEER_TIMEOUT: f32 = 5.0;
+
+// Ghosts
+const GHOST_ALPHA: f32 = 0.45;
+const GHOST_DEAD_ALPHA: f32 = 0.2;
+const GHOST_INTERPOLATION_DELAY: f32 = 0.1;
+const GHOST_SNAPSHOT_LIMIT: usize = 32;
+
+pub(crate) struct RacePlugin;
+
+impl Plugin for RacePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RaceNotice>()
+            .init_resource::<RaceHeartbeatTimer>()
+            .init_resource::<RaceStateTimer>()
+            .init_resource::<RaceDistance>()
+            .init_resource::<GhostSnapshots>()
+            .add_systems(
+                Update,
+                (
+                    receive_race_packets,
+                    expire_race_peers,
+                    send_race_heartbeat,
+                    broadcast_player_state
+                        .run_if(in_state(AppState::Playing).or(in_state(AppState::RetryMenu))),
+                )
+                    .chain()
+                    .distributive_run_if(resource_exists::<RaceSession>),
+            )
+            .add_systems(
+                Update,
+                sync_ghosts.run_if(in_state(AppState::Playing).or(in_state(AppState::RetryMenu))),
+            )
+            .add_systems(
+                FixedUpdate,
+                track_race_distance.run_if(in_state(PlayerState::Flapping)),
+            );
+    }
+}
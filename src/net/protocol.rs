@@ -0,0 +1,103 @@
+use super::*;
+
+pub(crate) enum RacePacket {
+    Join,
+    Welcome {
+        player_id: u8,
+    },
+    Start {
+        seed: u64,
+    },
+    /// A client's reply to each Start it has acted on, which stops the host resending it
+    StartAck {
+        seed: u64,
+    },
+    State(RacerState),
+    Leave {
+        player_id: u8,
+    },
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct RacerState {
+    pub(crate) player_id: u8,
+    pub(crate) distance: f32,
+    pub(crate) y: f32,
+    pub(crate) velocity: f32,
+    pub(crate) alive: bool,
+}
+
+const JOIN_TAG: u8 = 0;
+const WELCOME_TAG: u8 = 1;
+const START_TAG: u8 = 2;
+const STATE_TAG: u8 = 3;
+const LEAVE_TAG: u8 = 4;
+const START_ACK_TAG: u8 = 5;
+
+impl RacePacket {
+    pub(crate) fn encode(&self) -> Vec<u8> {
+        let mut bytes = PACKET_MAGIC.to_vec();
+        match self {
+            RacePacket::Join => bytes.push(JOIN_TAG),
+            RacePacket::Welcome { player_id } => {
+                bytes.push(WELCOME_TAG);
+                bytes.push(*player_id);
+            }
+            RacePacket::Start { seed } => {
+                bytes.push(START_TAG);
+                bytes.extend_from_slice(&seed.to_le_bytes());
+            }
+            RacePacket::StartAck { seed } => {
+                bytes.push(START_ACK_TAG);
+                bytes.extend_from_slice(&seed.to_le_bytes());
+            }
+            RacePacket::State(state) => {
+                bytes.push(STATE_TAG);
+                bytes.push(state.player_id);
+                bytes.extend_from_slice(&state.distance.to_le_bytes());
+                bytes.extend_from_slice(&state.y.to_le_bytes());
+                bytes.extend_from_slice(&state.velocity.to_le_bytes());
+                bytes.push(state.alive as u8);
+            }
+            RacePacket::Leave { player_id } => {
+                bytes.push(LEAVE_TAG);
+                bytes.push(*player_id);
+            }
+        }
+        bytes
+    }
+
+    pub(crate) fn decode(bytes: &[u8]) -> Option<RacePacket> {
+        let body = bytes.strip_prefix(&PACKET_MAGIC)?;
+        let (&tag, body) = body.split_first()?;
+        match tag {
+            JOIN_TAG => Some(RacePacket::Join),
+            WELCOME_TAG => Some(RacePacket::Welcome {
+                player_id: *body.first()?,
+            }),
+            START_TAG => Some(RacePacket::Start {
+                seed: u64::from_le_bytes(body.get(0..8)?.try_into().ok()?),
+            }),
+            START_ACK_TAG => Some(RacePacket::StartAck {
+                seed: u64::from_le_bytes(body.get(0..8)?.try_into().ok()?),
+            }),
+            STATE_TAG => Some(RacePacket::State(RacerState {
+                player_id: *body.first()?,
+                distance: read_f32(body, 1)?,
+                y: read_f32(body, 5)?,
+                velocity: read_f32(body, 9)?,
+                alive: *body.get(13)? != 0,
+            })),
+            LEAVE_TAG => Some(RacePacket::Leave {
+                player_id: *body.first()?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+fn read_f32(bytes: &[u8], offset: usize) -> Option<f32> {
+    Some(f32::from_le_bytes(
+        bytes.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
@@ -0,0 +1,353 @@
+use super::*;
+use std::io;
+use std::io::ErrorKind;
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4, UdpSocket};
+use std::time::Duration;
+
+#[derive(Clone, Copy)]
+pub(crate) enum RaceRole {
+    Host,
+    Client,
+}
+
+struct RacePeer {
+    id: u8,
+    address: SocketAddr,
+    last_heard: f32,
+    /// Whether the host is still waiting for this peer to acknowledge the race start
+    awaiting_start: bool,
+}
+
+#[derive(Resource)]
+pub(crate) struct RaceSession {
+    socket: UdpSocket,
+    pub(crate) role: RaceRole,
+    pub(crate) local_id: Option<u8>,
+    peers: Vec<RacePeer>,
+    next_peer_id: u8,
+    /// Seed of the race this session last started, which later Starts for it only acknowledge
+    started_seed: Option<u64>,
+}
+
+#[derive(Resource, Default)]
+pub(crate) struct RaceNotice(pub(crate) Option<TextKey>);
+
+#[derive(Resource)]
+pub(crate) struct RaceHeartbeatTimer(Timer);
+
+#[derive(Resource)]
+pub(crate) struct RaceStateTimer(Timer);
+
+impl RaceSession {
+    pub(crate) fn host() -> io::Result<RaceSession> {
+        let socket = UdpSocket::bind(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, RACE_PORT))?;
+        socket.set_nonblocking(true)?;
+        Ok(RaceSession {
+            socket,
+            role: RaceRole::Host,
+            local_id: Some(HOST_PLAYER_ID),
+            peers: Vec::new(),
+            next_peer_id: HOST_PLAYER_ID + 1,
+            started_seed: None,
+        })
+    }
+
+    pub(crate) fn join() -> io::Result<RaceSession> {
+        let socket = UdpSocket::bind(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0))?;
+        socket.set_nonblocking(true)?;
+        socket.set_broadcast(true)?;
+        Ok(RaceSession {
+            socket,
+            role: RaceRole::Client,
+            local_id: None,
+            peers: Vec::new(),
+            next_peer_id: HOST_PLAYER_ID + 1,
+            started_seed: None,
+        })
+    }
+
+    pub(crate) fn is_host(&self) -> bool {
+        matches!(self.role, RaceRole::Host)
+    }
+
+    pub(crate) fn is_connected(&self) -> bool {
+        self.local_id.is_some()
+    }
+
+    pub(crate) fn racer_count(&self) -> usize {
+        self.peers.len() + 1
+    }
+
+    /// Starts a race for everyone in the lobby, resent on the heartbeat until each peer replies
+    pub(crate) fn start(&mut self, seed: u64) {
+        self.started_seed = Some(seed);
+        for peer in self.peers.iter_mut() {
+            peer.awaiting_start = true;
+        }
+        self.send_to_peers(&RacePacket::Start { seed }, None);
+    }
+
+    fn resend_start(&self) {
+        let Some(seed) = self.started_seed else {
+            return;
+        };
+        for peer in self.peers.iter().filter(|peer| peer.awaiting_start) {
+            self.send(&RacePacket::Start { seed }, peer.address);
+        }
+    }
+
+    fn send(&self, packet: &RacePacket, address: SocketAddr) {
+        // Lost datagrams are expected on UDP and are covered by the next send
+        let _ = self.socket.send_to(&packet.encode(), address);
+    }
+
+    fn send_to_peers(&self, packet: &RacePacket, except: Option<u8>) {
+        for peer in self.peers.iter() {
+            if Some(peer.id) != except {
+                self.send(packet, peer.address);
+            }
+        }
+    }
+
+    fn peer_id(&self, address: SocketAddr) -> Option<u8> {
+        self.peers
+            .iter()
+            .find(|peer| peer.address == address)
+            .map(|peer| peer.id)
+    }
+
+    fn touch_peer(&mut self, address: SocketAddr, now: f32) {
+        if let Some(peer) = self.peers.iter_mut().find(|peer| peer.address == address) {
+            peer.last_heard = now;
+        }
+    }
+}
+
+impl Default for RaceHeartbeatTimer {
+    fn default() -> RaceHeartbeatTimer {
+        RaceHeartbeatTimer(Timer::new(
+            Duration::from_secs_f32(HEARTBEAT_INTERVAL),
+            TimerMode::Repeating,
+        ))
+    }
+}
+
+impl Default for RaceStateTimer {
+    fn default() -> RaceStateTimer {
+        RaceStateTimer(Timer::new(
+            Duration::from_secs_f32(STATE_SEND_INTERVAL),
+            TimerMode::Repeating,
+        ))
+    }
+}
+
+pub(crate) fn leave_race_session(mut commands: Commands, session: Option<Res<RaceSession>>) {
+    if let Some(session) = session {
+        if let Some(player_id) = session.local_id {
+            session.send_to_peers(&RacePacket::Leave { player_id }, None);
+        }
+        commands.remove_resource::<RaceSession>();
+    }
+}
+
+pub(crate) fn send_race_heartbeat(
+    session: Res<RaceSession>,
+    mut timer: ResMut<RaceHeartbeatTimer>,
+    time: Res<Time<Real>>,
+) {
+    if !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+    if let RaceRole::Host = session.role {
+        session.resend_start();
+    }
+    if let RaceRole::Client = session.role {
+        if session.peers.is_empty() {
+            session.send(
+                &RacePacket::Join,
+                SocketAddrV4::new(Ipv4Addr::BROADCAST, RACE_PORT).into(),
+            );
+            session.send(
+                &RacePacket::Join,
+                SocketAddrV4::new(Ipv4Addr::LOCALHOST, RACE_PORT).into(),
+            );
+        } else {
+            session.send_to_peers(&RacePacket::Join, None);
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn receive_race_packets(
+    mut commands: Commands,
+    mut session: ResMut<RaceSession>,
+    mut notice: ResMut<RaceNotice>,
+    mut snapshots: ResMut<GhostSnapshots>,
+    mut run_seed: ResMut<RunSeed>,
+    app_state: Res<State<AppState>>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+    time: Res<Time<Real>>,
+) {
+    let now = time.elapsed_secs();
+    let mut buffer = [0; MAX_PACKET_SIZE];
+    loop {
+        let (length, address) = match session.socket.recv_from(&mut buffer) {
+            Ok(received) => received,
+            Err(error) if error.kind() == ErrorKind::WouldBlock => break,
+            // Windows reports ICMP port unreachable replies as receive errors
+            Err(_) => continue,
+        };
+        let Some(packet) = RacePacket::decode(&buffer[..length]) else {
+            continue;
+        };
+        session.touch_peer(address, now);
+
+        match (session.role, packet) {
+            (RaceRole::Host, RacePacket::Join) => {
+                let player_id = match session.peer_id(address) {
+                    Some(player_id) => player_id,
+                    None if session.racer_count() < MAX_RACERS => {
+                        let player_id = session.next_peer_id;
+                        session.next_peer_id = session.next_peer_id.wrapping_add(1);
+                        session.peers.push(RacePeer {
+                            id: player_id,
+                            address,
+                            last_heard: now,
+                            awaiting_start: false,
+                        });
+                        player_id
+                    }
+                    None => continue,
+                };
+                session.send(&RacePacket::Welcome { player_id }, address);
+            }
+            (RaceRole::Client, RacePacket::Welcome { player_id }) if !session.is_connected() => {
+                session.local_id = Some(player_id);
+                session.peers.push(RacePeer {
+                    id: HOST_PLAYER_ID,
+                    address,
+                    last_heard: now,
+                    awaiting_start: false,
+                });
+            }
+            (RaceRole::Client, RacePacket::Start { seed })
+                if session.peer_id(address).is_some() =>
+            {
+                // Resent Starts for the race already underway are only acknowledged again
+                if session.started_seed != Some(seed) {
+                    if *app_state.get() != AppState::Lobby {
+                        continue;
+                    }
+                    session.started_seed = Some(seed);
+                    run_seed.0 = seed;
+                    next_app_state.set(AppState::Playing);
+                }
+                session.send(&RacePacket::StartAck { seed }, address);
+            }
+            (RaceRole::Host, RacePacket::StartAck { seed })
+                if session.started_seed == Some(seed) =>
+            {
+                if let Some(peer) = session
+                    .peers
+                    .iter_mut()
+                    .find(|peer| peer.address == address)
+                {
+                    peer.awaiting_start = false;
+                }
+            }
+            (role, RacePacket::State(state)) => {
+                let Some(sender_id) = session.peer_id(address) else {
+                    continue;
+                };
+                if let RaceRole::Host = role {
+                    if state.player_id != sender_id {
+                        continue;
+                    }
+                    session.send_to_peers(&RacePacket::State(state), Some(sender_id));
+                }
+                if Some(state.player_id) != session.local_id {
+                    snapshots.push(state, now);
+                }
+            }
+            (RaceRole::Host, RacePacket::Leave { player_id })
+                if session.peer_id(address) == Some(player_id) =>
+            {
+                session.peers.retain(|peer| peer.id != player_id);
+                session.send_to_peers(&RacePacket::Leave { player_id }, None);
+                snapshots.remove(player_id);
+            }
+            (RaceRole::Client, RacePacket::Leave { player_id }) => {
+                if player_id == HOST_PLAYER_ID {
+                    notice.0 = Some(TextKey::HostLeft);
+                    commands.remove_resource::<RaceSession>();
+                    return;
+                }
+                snapshots.remove(player_id);
+            }
+            _ => (),
+        }
+    }
+}
+
+pub(crate) fn expire_race_peers(
+    mut commands: Commands,
+    mut session: ResMut<RaceSession>,
+    mut notice: ResMut<RaceNotice>,
+    mut snapshots: ResMut<GhostSnapshots>,
+    time: Res<Time<Real>>,
+) {
+    let now = time.elapsed_secs();
+    let expired: Vec<u8> = session
+        .peers
+        .iter()
+        .filter(|peer| now - peer.last_heard > PEER_TIMEOUT)
+        .map(|peer| peer.id)
+        .collect();
+
+    for player_id in expired {
+        match session.role {
+            RaceRole::Host => {
+                session.peers.retain(|peer| peer.id != player_id);
+                session.send_to_peers(&RacePacket::Leave { player_id }, None);
+                snapshots.remove(player_id);
+            }
+            RaceRole::Client => {
+                notice.0 = Some(TextKey::HostLost);
+                commands.remove_resource::<RaceSession>();
+                return;
+            }
+        }
+    }
+}
+
+pub(crate) fn broadcast_player_state(
+    session: Res<RaceSession>,
+    mut timer: ResMut<RaceStateTimer>,
+    player_query: Query<(&Player, &Transform)>,
+    player_state: Res<State<PlayerState>>,
+    distance: Res<RaceDistance>,
+    time: Res<Time<Real>>,
+) {
+    if !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+    let (Some(player_id), Ok((player, player_transform))) =
+        (session.local_id, player_query.get_single())
+    else {
+        return;
+    };
+    let alive = matches!(
+        player_state.get(),
+        PlayerState::WaitingToStart | PlayerState::Flapping
+    );
+    session.send_to_peers(
+        &RacePacket::State(RacerState {
+            player_id,
+            distance: distance.0,
+            y: player_transform.translation.y,
+            velocity: player.velocity,
+            alive,
+        }),
+        None,
+    );
+}
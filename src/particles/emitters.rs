@@ -0,0 +1,75 @@
+use super::*;
+use rand::seq::SliceRandom;
+use rand::{thread_rng, Rng};
+
+/// Which burst from `assets/particles.ron` to play
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum ParticleBurst {
+    Flap,
+    Pass,
+    Crash,
+}
+
+/// Asks for a burst of particles at a point in the world
+#[derive(Event)]
+pub(crate) struct EmitParticles {
+    pub(crate) burst: ParticleBurst,
+    pub(crate) position: Vec2,
+}
+
+impl ParticleBurst {
+    fn emitter(self, effects: &ParticleEffects) -> &ParticleEmitter {
+        match self {
+            ParticleBurst::Flap => &effects.flap,
+            ParticleBurst::Pass => &effects.pass,
+            ParticleBurst::Crash => &effects.crash,
+        }
+    }
+}
+
+/// Takes particles from the pool for each burst, leaving bursts short when the pool runs dry
+pub(crate) fn emit_particles(
+    mut emit_events: EventReader<EmitParticles>,
+    mut particle_query: Query<(&mut Particle, &mut Transform, &mut Sprite, &mut Visibility)>,
+    mut pool: ResMut<ParticlePool>,
+    effects: Res<ParticleEffects>,
+) {
+    let mut rng = thread_rng();
+    for EmitParticles { burst, position } in emit_events.read() {
+        let emitter = burst.emitter(&effects);
+        let origin = *position + Vec2::new(emitter.offset.0, emitter.offset.1);
+        for _ in 0..emitter.count {
+            let Some(entity) = pool.free.pop() else {
+                break;
+            };
+            let Ok((mut particle, mut transform, mut sprite, mut visibility)) =
+                particle_query.get_mut(entity)
+            else {
+                continue;
+            };
+            let angle =
+                (emitter.direction + rng.gen_range(-0.5..=0.5) * emitter.spread).to_radians();
+            let speed = rng.gen_range(emitter.speed.0..=emitter.speed.1);
+            let color = emitter
+                .colors
+                .choose(&mut rng)
+                .map_or(Color::WHITE, |color| Color::srgb_from_array(*color));
+            *particle = Particle {
+                velocity: Vec2::from_angle(angle) * speed,
+                age: 0.0,
+                lifetime: rng.gen_range(emitter.lifetime.0..=emitter.lifetime.1),
+                gravity: emitter.gravity,
+                drag: emitter.drag,
+                spin: rng.gen_range(-emitter.spin..=emitter.spin).to_radians(),
+                size: emitter.size,
+                color,
+                fade: emitter.fade,
+            };
+            transform.translation = origin.extend(PARTICLE_SPRITE_Z);
+            transform.rotation = Quat::IDENTITY;
+            sprite.color = color;
+            sprite.custom_size = Some(Vec2::splat(emitter.size.0));
+            *visibility = Visibility::Inherited;
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use super::*;
+
+mod emitters;
+mod pool;
+
+// Re-exports
+pub(crate) use emitters::*;
+pub(crate) use pool::*;
+
+pub(crate) struct ParticlesPlugin;
+
+impl Plugin for ParticlesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<EmitParticles>()
+            .add_systems(Startup, spawn_particle_pool)
+            .add_systems(Update, (emit_particles, update_particles).chain());
+    }
+}
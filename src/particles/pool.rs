@@ -0,0 +1,85 @@
+use super::*;
+
+/// A pooled particle sprite, hidden while it is not in use
+#[derive(Component, Default)]
+pub(crate) struct Particle {
+    pub(crate) velocity: Vec2,
+    pub(crate) age: f32,
+    pub(crate) lifetime: f32,
+    pub(crate) gravity: f32,
+    pub(crate) drag: f32,
+    /// Turn per second in radians
+    pub(crate) spin: f32,
+    pub(crate) size: (f32, f32),
+    pub(crate) color: Color,
+    pub(crate) fade: bool,
+}
+
+/// Particles that are free to be emitted, every one of them spawned at startup
+#[derive(Resource)]
+pub(crate) struct ParticlePool {
+    pub(crate) free: Vec<Entity>,
+}
+
+pub(crate) fn spawn_particle_pool(mut commands: Commands) {
+    let mut free = Vec::with_capacity(PARTICLE_POOL_SIZE);
+    for _ in 0..PARTICLE_POOL_SIZE {
+        free.push(
+            commands
+                .spawn((
+                    Sprite::from_color(Color::WHITE, Vec2::ONE),
+                    Transform::from_xyz(0.0, 0.0, PARTICLE_SPRITE_Z),
+                    Visibility::Hidden,
+                    Particle::default(),
+                ))
+                .id(),
+        );
+    }
+    commands.insert_resource(ParticlePool { free });
+}
+
+/// Moves live particles and hands them back to the pool once they expire
+pub(crate) fn update_particles(
+    mut particle_query: Query<(
+        Entity,
+        &mut Particle,
+        &mut Transform,
+        &mut Sprite,
+        &mut Visibility,
+    )>,
+    mut pool: ResMut<ParticlePool>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_secs();
+    if delta == 0.0 {
+        return;
+    }
+    for (entity, mut particle, mut transform, mut sprite, mut visibility) in
+        particle_query.iter_mut()
+    {
+        if *visibility == Visibility::Hidden {
+            continue;
+        }
+        particle.age += delta;
+        if particle.age >= particle.lifetime {
+            *visibility = Visibility::Hidden;
+            pool.free.push(entity);
+            continue;
+        }
+
+        particle.velocity.y += particle.gravity * delta;
+        let velocity = particle.velocity * (1.0 - particle.drag * delta).max(0.0);
+        particle.velocity = velocity;
+        transform.translation += velocity.extend(0.0) * delta;
+        transform.rotate_z(particle.spin * delta);
+
+        let progress = particle.age / particle.lifetime;
+        let size = particle.size.0.lerp(particle.size.1, progress);
+        sprite.custom_size = Some(Vec2::splat(size));
+        sprite.color = if particle.fade {
+            particle.color.with_alpha(1.0 - progress)
+        } else {
+            particle.color
+        };
+    }
+}
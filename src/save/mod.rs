@@ -0,0 +1,62 @@
+use super::*;
+use bevy::utils::SystemTime;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+mod storage;
+
+// Re-exports
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use storage::data_file_path;
+
+// Storage
+const SAVE_DIRECTORY: &str = "flappy_bird";
+const SAVE_EXTENSION: &str = "ron";
+
+/// A resource that is read from storage on startup and written back whenever it changes
+pub(crate) trait Saved: Resource + Serialize + DeserializeOwned + Default {
+    const KEY: &'static str;
+}
+
+pub(crate) trait SavedAppExt {
+    fn init_saved<T: Saved>(&mut self) -> &mut Self;
+}
+
+impl SavedAppExt for App {
+    fn init_saved<T: Saved>(&mut self) -> &mut Self {
+        self.insert_resource(load_saved::<T>())
+            .add_systems(Last, write_saved::<T>.run_if(resource_changed::<T>))
+    }
+}
+
+/// Seconds since the Unix epoch, for the dates kept in saves
+pub(crate) fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+pub(crate) fn load_saved<T: Saved>() -> T {
+    let Some(contents) = storage::read(T::KEY) else {
+        return T::default();
+    };
+    ron::from_str(&contents).unwrap_or_else(|error| {
+        warn!("Discarding unreadable save data for {}: {error}", T::KEY);
+        T::default()
+    })
+}
+
+fn write_saved<T: Saved>(value: Res<T>) {
+    // Skip the write caused by inserting the freshly loaded resource
+    if value.is_added() {
+        return;
+    }
+    match ron::ser::to_string_pretty(value.as_ref(), Default::default()) {
+        Ok(contents) => {
+            if let Err(error) = storage::write(T::KEY, &contents) {
+                warn!("Could not save {}: {error}", T::KEY);
+            }
+        }
+        Err(error) => warn!("Could not serialize {}: {error}", T::KEY),
+    }
+}
@@ -0,0 +1,64 @@
+use super::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod backend {
+    use super::*;
+    use std::path::PathBuf;
+    use std::{fs, io};
+
+    /// Path of a file in the game's folder in the system data directory
+    pub(crate) fn data_file_path(file_name: &str) -> Option<PathBuf> {
+        let mut path = dirs::data_dir()?;
+        path.push(SAVE_DIRECTORY);
+        path.push(file_name);
+        Some(path)
+    }
+
+    fn save_path(key: &str) -> Option<PathBuf> {
+        let mut path = data_file_path(key)?;
+        path.set_extension(SAVE_EXTENSION);
+        Some(path)
+    }
+
+    pub(crate) fn read(key: &str) -> Option<String> {
+        fs::read_to_string(save_path(key)?).ok()
+    }
+
+    pub(crate) fn write(key: &str, contents: &str) -> io::Result<()> {
+        let path = save_path(key).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, "no data directory on this system")
+        })?;
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(path, contents)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod backend {
+    use super::*;
+    use std::io;
+    use web_sys::Storage;
+
+    fn local_storage() -> Option<Storage> {
+        web_sys::window()?.local_storage().ok()?
+    }
+
+    fn storage_key(key: &str) -> String {
+        format!("{SAVE_DIRECTORY}.{key}.{SAVE_EXTENSION}")
+    }
+
+    pub(crate) fn read(key: &str) -> Option<String> {
+        local_storage()?.get_item(&storage_key(key)).ok()?
+    }
+
+    pub(crate) fn write(key: &str, contents: &str) -> io::Result<()> {
+        local_storage()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "local storage is unavailable"))?
+            .set_item(&storage_key(key), contents)
+            .map_err(|_| io::Error::new(io::ErrorKind::Other, "local storage is full"))
+    }
+}
+
+pub(crate) use backend::*;
@@ -0,0 +1,113 @@
+use super::*;
+
+/// Colors of the playfield, swapped out as a whole by the high-contrast setting
+pub(crate) struct Palette {
+    pub(crate) background: [f32; 3],
+    /// Multiplied into the pipe sprites
+    pub(crate) pipe_tint: [f32; 3],
+    /// Multiplied into the bird sprite
+    pub(crate) player_tint: [f32; 3],
+}
+
+impl Settings {
+    pub(crate) fn palette(&self) -> &'static Palette {
+        if self.high_contrast {
+            &HIGH_CONTRAST_PALETTE
+        } else {
+            &STANDARD_PALETTE
+        }
+    }
+
+    /// Scale for the bird's collision radius against pipes
+    pub(crate) fn collision_scale(&self) -> f32 {
+        if self.forgiving_collisions {
+            FORGIVING_COLLISION_SCALE
+        } else {
+            1.0
+        }
+    }
+
+    /// The speed the assist runs the game at, kept within [`GAME_SPEED_STEPS`] whatever the save
+    /// holds since time can't run at zero, negative or NaN speed
+    pub(crate) fn game_speed(&self) -> f32 {
+        let slowest = GAME_SPEED_STEPS[0];
+        let fastest = GAME_SPEED_STEPS[GAME_SPEED_STEPS.len() - 1];
+        if self.game_speed.is_nan() {
+            fastest
+        } else {
+            self.game_speed.clamp(slowest, fastest)
+        }
+    }
+}
+
+/// The assists the current run is flown with, fixed as it starts
+#[derive(Resource)]
+pub(crate) struct RunAssists {
+    pub(crate) game_speed: f32,
+    /// Scale for the bird's collision radius against pipes
+    pub(crate) collision_scale: f32,
+}
+
+impl Default for RunAssists {
+    fn default() -> RunAssists {
+        RunAssists {
+            game_speed: 1.0,
+            collision_scale: 1.0,
+        }
+    }
+}
+
+impl RunAssists {
+    /// Whether the run is being made easier, which marks its score
+    pub(crate) fn is_assisted(&self) -> bool {
+        self.game_speed < 1.0 || self.collision_scale < 1.0
+    }
+}
+
+// Races are flown without assists so every racer plays by the same rules
+pub(crate) fn choose_run_assists(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    race_session: Option<Res<RaceSession>>,
+) {
+    commands.insert_resource(match race_session {
+        Some(_) => RunAssists::default(),
+        None => RunAssists {
+            game_speed: settings.game_speed(),
+            collision_scale: settings.collision_scale(),
+        },
+    });
+}
+
+/// Colors the sky, pipes and bird from the palette, including pipes and birds spawned since
+#[allow(clippy::type_complexity)]
+pub(crate) fn apply_palette(
+    mut background_query: Query<&mut Sprite, With<PlayfieldBackground>>,
+    mut pipe_query: Query<&mut Sprite, (With<Pipe>, Without<PlayfieldBackground>)>,
+    mut player_query: Query<
+        &mut Sprite,
+        (With<Player>, Without<Pipe>, Without<PlayfieldBackground>),
+    >,
+    added_query: Query<(), Or<(Added<Pipe>, Added<Player>, Added<PlayfieldBackground>)>>,
+    settings: Res<Settings>,
+) {
+    if !settings.is_changed() && added_query.is_empty() {
+        return;
+    }
+    let palette = settings.palette();
+    let tint = |sprite: &mut Sprite, color: [f32; 3]| {
+        let color = Color::srgb_from_array(color);
+        if sprite.color != color {
+            sprite.color = color;
+        }
+    };
+    for mut sprite in background_query.iter_mut() {
+        tint(&mut sprite, palette.background);
+    }
+    for mut sprite in pipe_query.iter_mut() {
+        tint(&mut sprite, palette.pipe_tint);
+    }
+    for mut sprite in player_query.iter_mut() {
+        tint(&mut sprite, palette.player_tint);
+    }
+}
@@ -0,0 +1,145 @@
+use super::*;
+use bevy::image::{ImageSampler, ImageSamplerDescriptor};
+use bevy::window::{MonitorSelection, PresentMode, PrimaryWindow, WindowMode};
+
+/// Window mode for a fullscreen setting
+pub(crate) fn window_mode(fullscreen: bool) -> WindowMode {
+    if fullscreen {
+        WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+    } else {
+        WindowMode::Windowed
+    }
+}
+
+pub(crate) fn present_mode(vsync: bool) -> PresentMode {
+    if vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    }
+}
+
+// The browser can leave fullscreen on its own, so the toggle starts from what is actually shown
+#[cfg(not(target_arch = "wasm32"))]
+fn is_fullscreen(window: &Window) -> bool {
+    window.mode != WindowMode::Windowed
+}
+
+#[cfg(target_arch = "wasm32")]
+fn is_fullscreen(_window: &Window) -> bool {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .is_some_and(|document| document.fullscreen_element().is_some())
+}
+
+pub(crate) fn handle_fullscreen_keys(
+    keys: Res<ButtonInput<KeyCode>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut settings: ResMut<Settings>,
+    mut fullscreen_override: ResMut<FullscreenOverride>,
+) {
+    let alt_enter = keys.any_pressed([KeyCode::AltLeft, KeyCode::AltRight])
+        && keys.just_pressed(KeyCode::Enter);
+    if !keys.just_pressed(FULLSCREEN_KEY) && !alt_enter {
+        return;
+    }
+    if let Ok(window) = window_query.get_single() {
+        settings.fullscreen = !is_fullscreen(window);
+        fullscreen_override.0 = None;
+    }
+}
+
+pub(crate) fn apply_window_settings(
+    settings: Res<Settings>,
+    fullscreen_override: Res<FullscreenOverride>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Ok(mut window) = window_query.get_single_mut() else {
+        return;
+    };
+    let present_mode = present_mode(settings.vsync);
+    if window.present_mode != present_mode {
+        window.present_mode = present_mode;
+    }
+    let fullscreen = fullscreen_override.fullscreen(&settings);
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let mode = window_mode(fullscreen);
+        if window.mode != mode {
+            window.mode = mode;
+        }
+    }
+    #[cfg(target_arch = "wasm32")]
+    if fullscreen != is_fullscreen(&window) {
+        request_canvas_fullscreen(fullscreen);
+    }
+}
+
+/// Asks the browser to show only the game canvas, or to go back to the page
+#[cfg(target_arch = "wasm32")]
+fn request_canvas_fullscreen(fullscreen: bool) {
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return;
+    };
+    if !fullscreen {
+        document.exit_fullscreen();
+        return;
+    }
+    // Browsers refuse requests that do not come from a key press or click, such as one made on startup
+    match document.query_selector(CANVAS_ID) {
+        Ok(Some(canvas)) => {
+            if canvas.request_fullscreen().is_err() {
+                warn!("The browser refused to show the game in fullscreen");
+            }
+        }
+        _ => warn!("Could not find the {CANVAS_ID} canvas to show in fullscreen"),
+    }
+}
+
+/// Sets the sampler of every image, including ones loaded after the setting changed
+pub(crate) fn apply_texture_filtering(
+    settings: Res<Settings>,
+    mut image_events: EventReader<AssetEvent<Image>>,
+    mut images: ResMut<Assets<Image>>,
+    mut applied: Local<Option<TextureFiltering>>,
+) {
+    let descriptor = match settings.filtering {
+        TextureFiltering::Sharp => ImageSamplerDescriptor::nearest(),
+        TextureFiltering::Smooth => ImageSamplerDescriptor::linear(),
+    };
+    if *applied != Some(settings.filtering) {
+        *applied = Some(settings.filtering);
+        image_events.clear();
+        for (_, image) in images.iter_mut() {
+            image.sampler = ImageSampler::Descriptor(descriptor.clone());
+        }
+        return;
+    }
+    for event in image_events.read() {
+        if let AssetEvent::Added { id } = event {
+            if let Some(image) = images.get_mut(*id) {
+                image.sampler = ImageSampler::Descriptor(descriptor.clone());
+            }
+        }
+    }
+}
+
+/// Sleeps away the rest of each frame that finished early, as the window does not cap frames itself
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn limit_frame_rate(
+    settings: Res<Settings>,
+    mut last_frame: Local<Option<std::time::Instant>>,
+) {
+    let Some(frame_cap) = settings.frame_cap else {
+        *last_frame = None;
+        return;
+    };
+    let frame_duration = std::time::Duration::from_secs_f64(1.0 / frame_cap as f64);
+    if let Some(last_frame) = *last_frame {
+        let elapsed = last_frame.elapsed();
+        if elapsed < frame_duration {
+            std::thread::sleep(frame_duration - elapsed);
+        }
+    }
+    *last_frame = Some(std::time::Instant::now());
+}
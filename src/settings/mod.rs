@@ -0,0 +1,151 @@
+use super::*;
+use serde::{Deserialize, Serialize};
+
+mod accessibility;
+mod display;
+
+// Re-exports
+pub(crate) use accessibility::*;
+pub(crate) use display::*;
+
+// Display
+pub(crate) const FRAME_CAP_CHOICES: [Option<u32>; 5] =
+    [None, Some(30), Some(60), Some(120), Some(144)];
+const FULLSCREEN_KEY: KeyCode = KeyCode::F11;
+
+// Accessibility
+pub(crate) const STANDARD_PALETTE: Palette = Palette {
+    background: [0.565, 0.855, 1.0], // #90daff
+    pipe_tint: [1.0, 1.0, 1.0],
+    player_tint: [1.0, 1.0, 1.0],
+};
+/// A black sky, with tints above 1.0 brightening the pipes and the bird against it
+pub(crate) const HIGH_CONTRAST_PALETTE: Palette = Palette {
+    background: [0.0, 0.0, 0.0],
+    pipe_tint: [1.4, 1.4, 1.4],
+    player_tint: [1.25, 1.25, 1.25],
+};
+/// Steps the Settings menu offers for the game speed
+pub(crate) const GAME_SPEED_STEPS: [f32; 3] = [0.7, 0.85, 1.0];
+/// Share of the bird's collision radius that still counts with forgiving collisions on
+const FORGIVING_COLLISION_SCALE: f32 = 0.7;
+
+/// Fullscreen asked for on the command line, which is never saved and wins over
+/// [`Settings::fullscreen`] until the player toggles fullscreen themselves
+#[derive(Resource, Default)]
+pub(crate) struct FullscreenOverride(pub(crate) Option<bool>);
+
+impl FullscreenOverride {
+    /// Whether the window should be fullscreen right now
+    pub(crate) fn fullscreen(&self, settings: &Settings) -> bool {
+        self.0.unwrap_or(settings.fullscreen)
+    }
+}
+
+/// Options picked on the Settings menu
+#[derive(Resource, Serialize, Deserialize, Clone)]
+// Settings added in later versions fall back to their defaults in older saves
+#[serde(default)]
+pub(crate) struct Settings {
+    pub(crate) scaling: PlayfieldScaling,
+    /// Gives menus and endless runs a taller playfield when the window is taller than it is wide
+    pub(crate) portrait: bool,
+    pub(crate) fullscreen: bool,
+    pub(crate) vsync: bool,
+    /// Frames per second to stay under, `None` for no limit
+    pub(crate) frame_cap: Option<u32>,
+    pub(crate) filtering: TextureFiltering,
+    pub(crate) master_volume: f32,
+    pub(crate) music_volume: f32,
+    pub(crate) sfx_volume: f32,
+    pub(crate) ui_volume: f32,
+    /// Turns shake, flashes and hit-stop off altogether for players they bother
+    pub(crate) screen_effects: bool,
+    pub(crate) shake_intensity: f32,
+    pub(crate) flash_intensity: f32,
+    pub(crate) hit_stop_intensity: f32,
+    /// Key of the interface language from [`LANGUAGES`]
+    pub(crate) language: String,
+    pub(crate) high_contrast: bool,
+    /// Keeps the bird level and turns shake and flashes off
+    pub(crate) reduced_motion: bool,
+    /// Speed runs are played at, below 1.0 to give more time to react
+    pub(crate) game_speed: f32,
+    /// Shrinks the bird's hitbox so that near misses against pipes are let off
+    pub(crate) forgiving_collisions: bool,
+}
+
+impl Saved for Settings {
+    const KEY: &'static str = "settings";
+}
+
+impl Default for Settings {
+    fn default() -> Settings {
+        Settings {
+            scaling: PlayfieldScaling::default(),
+            portrait: true,
+            fullscreen: false,
+            vsync: true,
+            frame_cap: None,
+            filtering: TextureFiltering::default(),
+            master_volume: 1.0,
+            music_volume: 1.0,
+            sfx_volume: 1.0,
+            ui_volume: 1.0,
+            screen_effects: true,
+            shake_intensity: 1.0,
+            flash_intensity: 1.0,
+            hit_stop_intensity: 1.0,
+            language: DEFAULT_LANGUAGE.to_string(),
+            high_contrast: false,
+            reduced_motion: false,
+            game_speed: 1.0,
+            forgiving_collisions: false,
+        }
+    }
+}
+
+/// How the playfield is stretched to fill the window
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum PlayfieldScaling {
+    /// As large as fits, which may blur pixels at fractional scales
+    #[default]
+    Fit,
+    /// The largest whole multiple that fits, keeping every pixel the same size
+    Integer,
+}
+
+/// How textures are sampled when they are drawn larger than their pixels
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum TextureFiltering {
+    /// Hard pixel edges
+    #[default]
+    Sharp,
+    /// Blended pixels
+    Smooth,
+}
+
+pub(crate) struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_saved::<Settings>()
+            .init_resource::<FullscreenOverride>()
+            .add_systems(
+                Update,
+                (
+                    handle_fullscreen_keys,
+                    apply_window_settings.run_if(
+                        resource_changed::<Settings>.or(resource_changed::<FullscreenOverride>),
+                    ),
+                    apply_texture_filtering,
+                )
+                    .chain(),
+            )
+            .add_systems(Update, apply_palette)
+            .init_resource::<RunAssists>()
+            .add_systems(OnEnter(AppState::Playing), choose_run_assists);
+        #[cfg(not(target_arch = "wasm32"))]
+        app.add_systems(Last, limit_frame_rate);
+    }
+}
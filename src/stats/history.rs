@@ -0,0 +1,186 @@
+use super::*;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::{fs, io};
+use thiserror::Error;
+
+/// One finished run as written to exported run logs
+#[derive(Serialize, Deserialize, Clone)]
+pub(crate) struct RunRecord {
+    /// Seconds since the Unix epoch when the run ended
+    pub(crate) timestamp: u64,
+    pub(crate) seed: u64,
+    pub(crate) mode: String,
+    pub(crate) score: u32,
+    pub(crate) duration: f32,
+    pub(crate) flaps: u32,
+    /// `None` when the run ended at a finish line
+    pub(crate) cause: Option<DeathCause>,
+    /// Distance flown along the course when the bird crashed
+    pub(crate) death_x: Option<f32>,
+    /// Height of the bird when it crashed
+    pub(crate) death_y: Option<f32>,
+    pub(crate) version: String,
+    /// Whether the speed assist or forgiving collisions were on
+    #[serde(default)]
+    pub(crate) assisted: bool,
+}
+
+/// The last [`RUN_HISTORY_LEN`] finished runs, oldest first
+#[derive(Resource, Serialize, Deserialize, Default)]
+pub(crate) struct RunHistory {
+    pub(crate) runs: Vec<RunRecord>,
+}
+
+impl Saved for RunHistory {
+    const KEY: &'static str = "history";
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum ExportFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Error)]
+pub enum HistoryExportError {
+    #[error("unknown export format for {0}, expected a .csv or .json file")]
+    UnknownFormat(String),
+    #[error("could not write run history: {0}")]
+    Io(#[from] io::Error),
+    #[error("could not serialize run history: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+impl ExportFormat {
+    pub(crate) fn from_path(path: &Path) -> Option<ExportFormat> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "csv" => Some(ExportFormat::Csv),
+            "json" => Some(ExportFormat::Json),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+impl RunHistory {
+    pub(crate) fn export(&self, path: &Path) -> Result<(), HistoryExportError> {
+        let format = ExportFormat::from_path(path)
+            .ok_or_else(|| HistoryExportError::UnknownFormat(path.display().to_string()))?;
+        let contents = match format {
+            ExportFormat::Csv => self.to_csv(),
+            ExportFormat::Json => serde_json::to_string_pretty(&self.runs)?,
+        };
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
+    fn to_csv(&self) -> String {
+        let mut csv = String::from(RUN_HISTORY_CSV_HEADER);
+        csv.push('\n');
+        for run in self.runs.iter() {
+            let fields = [
+                run.timestamp.to_string(),
+                run.seed.to_string(),
+                csv_field(&run.mode),
+                run.score.to_string(),
+                run.duration.to_string(),
+                run.flaps.to_string(),
+                run.cause
+                    .map_or(String::new(), |cause| format!("{cause:?}")),
+                run.death_x.map_or(String::new(), |x| x.to_string()),
+                run.death_y.map_or(String::new(), |y| y.to_string()),
+                csv_field(&run.version),
+                run.assisted.to_string(),
+            ];
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+/// Quotes a field that would otherwise break the row, such as a level file name with a comma
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Writes the saved run history to `path` as CSV or JSON depending on its extension
+pub fn export_saved_run_history(path: &Path) -> Result<(), HistoryExportError> {
+    load_saved::<RunHistory>().export(path)
+}
+
+fn run_mode(game_mode: &GameMode, racing: bool) -> String {
+    match game_mode {
+        GameMode::Endless if racing => RACE_MODE_NAME.to_string(),
+        GameMode::Endless => ENDLESS_MODE_NAME.to_string(),
+        GameMode::Level(level) => format!("{LEVEL_MODE_NAME}:{}", level.key),
+        GameMode::Playtest { .. } => PLAYTEST_MODE_NAME.to_string(),
+    }
+}
+
+pub(crate) fn record_run_history(
+    mut run_ended_events: EventReader<RunEnded>,
+    mut history: ResMut<RunHistory>,
+    game_mode: Res<GameMode>,
+    run_seed: Res<RunSeed>,
+    race_session: Option<Res<RaceSession>>,
+    assists: Res<RunAssists>,
+) {
+    for run_ended in run_ended_events.read() {
+        if matches!(*game_mode, GameMode::Playtest { .. }) {
+            continue;
+        }
+        let crashed = run_ended.cause.is_some();
+        history.runs.push(RunRecord {
+            timestamp: unix_timestamp(),
+            seed: run_seed.0,
+            mode: run_mode(&game_mode, race_session.is_some()),
+            score: run_ended.score,
+            duration: run_ended.duration,
+            flaps: run_ended.flaps,
+            cause: run_ended.cause,
+            death_x: crashed.then_some(run_ended.position.x),
+            death_y: crashed.then_some(run_ended.position.y),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            assisted: assists.is_assisted(),
+        });
+        let excess = history.runs.len().saturating_sub(RUN_HISTORY_LEN);
+        history.runs.drain(..excess);
+    }
+}
+
+/// Writes the run history next to the save data, returning the name of the written file
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn export_run_history_file(
+    history: &RunHistory,
+    format: ExportFormat,
+) -> Result<String, HistoryExportError> {
+    let file_name = format!("{RUN_HISTORY_EXPORT_NAME}.{}", format.extension());
+    let path = data_file_path(&file_name).ok_or_else(|| {
+        io::Error::new(io::ErrorKind::NotFound, "no data directory on this system")
+    })?;
+    history.export(&path)?;
+    Ok(file_name)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn export_run_history_file(
+    _history: &RunHistory,
+    _format: ExportFormat,
+) -> Result<String, HistoryExportError> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "browsers cannot write files").into())
+}
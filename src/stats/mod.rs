@@ -0,0 +1,33 @@
+use super::*;
+
+mod history;
+mod player_stats;
+
+// Re-exports
+pub(crate) use history::*;
+pub use history::{export_saved_run_history, HistoryExportError};
+pub(crate) use player_stats::*;
+
+// Player Stats
+pub(crate) const RECENT_SCORES_LEN: usize = 50;
+
+// Run History
+/// Runs kept in the run log, since the whole log is rewritten to the save after every run
+pub(crate) const RUN_HISTORY_LEN: usize = 1000;
+const RUN_HISTORY_CSV_HEADER: &str =
+    "timestamp,seed,mode,score,duration,flaps,cause,death_x,death_y,version,assisted";
+const RUN_HISTORY_EXPORT_NAME: &str = "run_history";
+const ENDLESS_MODE_NAME: &str = "endless";
+const RACE_MODE_NAME: &str = "race";
+const LEVEL_MODE_NAME: &str = "level";
+const PLAYTEST_MODE_NAME: &str = "playtest";
+
+pub(crate) struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_saved::<PlayerStats>()
+            .init_saved::<RunHistory>()
+            .add_systems(Update, (record_run_stats, record_run_history));
+    }
+}
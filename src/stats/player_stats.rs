@@ -0,0 +1,90 @@
+use super::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+
+/// Lifetime totals over every finished run
+#[derive(Resource, Serialize, Deserialize, Default)]
+pub(crate) struct PlayerStats {
+    pub(crate) total_runs: u32,
+    pub(crate) total_flaps: u64,
+    pub(crate) total_pipes: u64,
+    pub(crate) total_play_time: f64,
+    pub(crate) longest_survival: f32,
+    total_score: u64,
+    /// How many runs ended on each score, enough to find the median without keeping every run
+    score_counts: BTreeMap<u32, u32>,
+    death_causes: BTreeMap<DeathCause, u32>,
+    /// The last [`RECENT_SCORES_LEN`] scores, oldest first
+    pub(crate) recent_scores: VecDeque<u32>,
+}
+
+impl Saved for PlayerStats {
+    const KEY: &'static str = "stats";
+}
+
+impl PlayerStats {
+    fn record(&mut self, run: &RunEnded) {
+        self.total_runs += 1;
+        self.total_flaps += run.flaps as u64;
+        self.total_pipes += run.pipes as u64;
+        self.total_play_time += run.duration as f64;
+        self.longest_survival = self.longest_survival.max(run.duration);
+        self.total_score += run.score as u64;
+        *self.score_counts.entry(run.score).or_default() += 1;
+        if let Some(cause) = run.cause {
+            *self.death_causes.entry(cause).or_default() += 1;
+        }
+        self.recent_scores.push_back(run.score);
+        while self.recent_scores.len() > RECENT_SCORES_LEN {
+            self.recent_scores.pop_front();
+        }
+    }
+
+    pub(crate) fn average_score(&self) -> f32 {
+        if self.total_runs == 0 {
+            return 0.0;
+        }
+        self.total_score as f32 / self.total_runs as f32
+    }
+
+    pub(crate) fn median_score(&self) -> f32 {
+        let total_runs: u32 = self.score_counts.values().sum();
+        if total_runs == 0 {
+            return 0.0;
+        }
+        // The lower and upper middle runs are the same one when the count is odd
+        let lower = self.nth_score((total_runs - 1) / 2);
+        let upper = self.nth_score(total_runs / 2);
+        (lower + upper) as f32 / 2.0
+    }
+
+    /// The score of the `index`th run when all runs are sorted by score
+    fn nth_score(&self, index: u32) -> u32 {
+        let mut seen = 0;
+        for (score, count) in self.score_counts.iter() {
+            seen += count;
+            if seen > index {
+                return *score;
+            }
+        }
+        0
+    }
+
+    pub(crate) fn deaths(&self, cause: DeathCause) -> u32 {
+        self.death_causes.get(&cause).copied().unwrap_or(0)
+    }
+}
+
+// Editor playtests can start anywhere along a level, so they are left out like they are for achievements
+pub(crate) fn record_run_stats(
+    mut run_ended_events: EventReader<RunEnded>,
+    mut stats: ResMut<PlayerStats>,
+    game_mode: Res<GameMode>,
+) {
+    for run_ended in run_ended_events.read() {
+        if matches!(*game_mode, GameMode::Playtest { .. }) {
+            continue;
+        }
+        stats.record(run_ended);
+    }
+}
@@ -0,0 +1,149 @@
+use super::*;
+
+#[derive(Component)]
+pub(crate) struct AchievementsMenu;
+
+pub(crate) fn spawn_achievements_menu(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    locale: Res<Locale>,
+    list: Res<AchievementList>,
+    progress: Res<AchievementProgress>,
+) {
+    commands
+        .spawn((
+            Node {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(ACHIEVEMENT_ROW_GAP_PX),
+                padding: UiRect::top(Val::Px(MENU_TOP_PADDING_PX)),
+                ..Default::default()
+            },
+            AchievementsMenu,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        justify_self: JustifySelf::Center,
+                        top: Val::Px(TITLE_TOP_MARGIN_PX),
+                        ..Default::default()
+                    },
+                    LocalizedText(TextKey::Awards),
+                ))
+                .with_children(|parent| {
+                    spawn_outlined_text(
+                        parent,
+                        locale.text(TextKey::Awards),
+                        fonts.title_font.clone(),
+                        TITLE_FONT_SIZE_PX,
+                        TITLE_COLOR,
+                        TITLE_OUTLINE_COLOR,
+                        TITLE_OUTLINE_WIDTH_PX,
+                    );
+                });
+            for achievement in list.achievements.iter() {
+                let unlocked_at = progress.unlocked.get(&achievement.key);
+                let text_color = match unlocked_at {
+                    Some(_) => TITLE_COLOR,
+                    None => ACHIEVEMENT_LOCKED_COLOR,
+                };
+                let status = unlocked_at.map_or(
+                    locale.text(TextKey::AchievementLocked).to_string(),
+                    |timestamp| format_date(*timestamp),
+                );
+                parent
+                    .spawn(Node {
+                        display: Display::Flex,
+                        align_items: AlignItems::Center,
+                        column_gap: Val::Px(ACHIEVEMENT_ROW_GAP_PX),
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        let columns = [
+                            (
+                                locale.achievement_name(&achievement.key),
+                                ACHIEVEMENT_NAME_WIDTH_PX,
+                                ACHIEVEMENT_NAME_FONT_SIZE_PX,
+                            ),
+                            (
+                                locale.achievement_description(&achievement.key),
+                                ACHIEVEMENT_DESCRIPTION_WIDTH_PX,
+                                ACHIEVEMENT_DESCRIPTION_FONT_SIZE_PX,
+                            ),
+                            (
+                                status.as_str(),
+                                ACHIEVEMENT_STATUS_WIDTH_PX,
+                                ACHIEVEMENT_NAME_FONT_SIZE_PX,
+                            ),
+                        ];
+                        for (text, width, font_size) in columns {
+                            parent
+                                .spawn(Node {
+                                    width: Val::Px(width),
+                                    ..Default::default()
+                                })
+                                .with_children(|parent| {
+                                    spawn_outlined_text(
+                                        parent,
+                                        text,
+                                        fonts.button_font.clone(),
+                                        font_size,
+                                        text_color,
+                                        TITLE_OUTLINE_COLOR,
+                                        ACHIEVEMENT_OUTLINE_WIDTH_PX,
+                                    );
+                                });
+                        }
+                    });
+            }
+            spawn_sized_button(
+                parent,
+                locale.text(TextKey::Back),
+                fonts.button_font.clone(),
+                SMALL_BUTTON_WIDTH_PX,
+                SMALL_BUTTON_HEIGHT_PX,
+                SMALL_BUTTON_FONT_SIZE_PX,
+                (
+                    LocalizedText(TextKey::Back),
+                    ButtonAction::open(AppState::MainMenu),
+                    CancelButton,
+                ),
+            );
+        });
+}
+
+pub(crate) fn despawn_achievements_menu(
+    mut commands: Commands,
+    achievements_menu_query: Query<Entity, With<AchievementsMenu>>,
+) {
+    if let Ok(achievements_menu) = achievements_menu_query.get_single() {
+        commands.entity(achievements_menu).despawn_recursive();
+    }
+}
+
+/// Formats a Unix timestamp as a UTC `YYYY-MM-DD` date
+pub(crate) fn format_date(timestamp: u64) -> String {
+    // Converts days since 1970-01-01 to a civil date, see
+    // https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = (timestamp / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
@@ -0,0 +1,242 @@
+use super::*;
+
+#[derive(Component)]
+pub(crate) struct BirdShop;
+
+#[derive(Component)]
+pub(crate) struct WalletText;
+
+/// Buys or equips the skin at this index of [`SKINS`]
+#[derive(Component)]
+pub(crate) struct SkinButton(usize);
+
+pub(crate) fn spawn_bird_shop(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    locale: Res<Locale>,
+    sprites: Res<SpriteAssets>,
+    skin_assets: Res<SkinAssets>,
+    skins: Res<SkinCollection>,
+    wallet: Res<Wallet>,
+) {
+    commands
+        .spawn((
+            Node {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(BIRD_SHOP_ROW_GAP_PX),
+                padding: UiRect::top(Val::Px(MENU_TOP_PADDING_PX)),
+                ..Default::default()
+            },
+            BirdShop,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        justify_self: JustifySelf::Center,
+                        top: Val::Px(TITLE_TOP_MARGIN_PX),
+                        ..Default::default()
+                    },
+                    LocalizedText(TextKey::Birds),
+                ))
+                .with_children(|parent| {
+                    spawn_outlined_text(
+                        parent,
+                        locale.text(TextKey::Birds),
+                        fonts.title_font.clone(),
+                        TITLE_FONT_SIZE_PX,
+                        TITLE_COLOR,
+                        TITLE_OUTLINE_COLOR,
+                        TITLE_OUTLINE_WIDTH_PX,
+                    );
+                });
+            parent
+                .spawn(Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(SCORE_DISPLAY_TOP_MARGIN_PX),
+                    right: Val::Px(COIN_DISPLAY_RIGHT_MARGIN_PX),
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(COIN_DISPLAY_GAP_PX),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        Node {
+                            width: Val::Px(COIN_ICON_SIZE_PX),
+                            height: Val::Px(COIN_ICON_SIZE_PX),
+                            ..Default::default()
+                        },
+                        ImageNode::new(sprites.coin_sprite.clone()),
+                    ));
+                    parent
+                        .spawn((Node::default(), WalletText))
+                        .with_children(|parent| {
+                            spawn_outlined_text(
+                                parent,
+                                &wallet.coins.to_string(),
+                                fonts.title_font.clone(),
+                                COIN_DISPLAY_FONT_SIZE_PX,
+                                SCORE_DISPLAY_COLOR,
+                                SCORE_DISPLAY_OUTLINE_COLOR,
+                                SCORE_DISPLAY_OUTLINE_WIDTH_PX,
+                            );
+                        });
+                });
+            for (index, skin) in SKINS.iter().enumerate() {
+                parent
+                    .spawn(Node {
+                        display: Display::Flex,
+                        align_items: AlignItems::Center,
+                        column_gap: Val::Px(SKIN_ROW_GAP_PX),
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Node {
+                                width: Val::Px(PLAYER_WIDTH_PX * SKIN_BIRD_PREVIEW_SCALE),
+                                height: Val::Px(PLAYER_HEIGHT_PX * SKIN_BIRD_PREVIEW_SCALE),
+                                ..Default::default()
+                            },
+                            ImageNode::new(skin_assets.bird_sprites[index].clone()),
+                        ));
+                        // Only the rim at the top of the pipe sprite is shown
+                        parent.spawn((
+                            Node {
+                                width: Val::Px(PIPE_WIDTH_PX * SKIN_PIPE_PREVIEW_SCALE),
+                                height: Val::Px(
+                                    SKIN_PIPE_PREVIEW_HEIGHT_PX * SKIN_PIPE_PREVIEW_SCALE,
+                                ),
+                                ..Default::default()
+                            },
+                            ImageNode {
+                                rect: Some(Rect::new(
+                                    0.0,
+                                    0.0,
+                                    PIPE_WIDTH_PX,
+                                    SKIN_PIPE_PREVIEW_HEIGHT_PX,
+                                )),
+                                ..ImageNode::new(skin_assets.pipe_sprites[index].clone())
+                            },
+                        ));
+                        parent
+                            .spawn(Node {
+                                width: Val::Px(SKIN_NAME_WIDTH_PX),
+                                ..Default::default()
+                            })
+                            .with_children(|parent| {
+                                spawn_outlined_text(
+                                    parent,
+                                    locale.skin_name(skin.key),
+                                    fonts.button_font.clone(),
+                                    SKIN_NAME_FONT_SIZE_PX,
+                                    TITLE_COLOR,
+                                    TITLE_OUTLINE_COLOR,
+                                    SKIN_NAME_OUTLINE_WIDTH_PX,
+                                );
+                            });
+                        spawn_sized_button(
+                            parent,
+                            &skin_button_text(skin, &skins, &locale),
+                            fonts.button_font.clone(),
+                            SKIN_BUTTON_WIDTH_PX,
+                            SKIN_BUTTON_HEIGHT_PX,
+                            SKIN_BUTTON_FONT_SIZE_PX,
+                            (
+                                SkinButton(index),
+                                ButtonAction::run_with_button(handle_skin_button),
+                            ),
+                        );
+                    });
+            }
+            spawn_button(
+                parent,
+                locale.text(TextKey::Back),
+                fonts.button_font.clone(),
+                (
+                    LocalizedText(TextKey::Back),
+                    ButtonAction::open(AppState::MainMenu),
+                    CancelButton,
+                ),
+            );
+        });
+}
+
+pub(crate) fn despawn_bird_shop(
+    mut commands: Commands,
+    bird_shop_query: Query<Entity, With<BirdShop>>,
+) {
+    if let Ok(bird_shop) = bird_shop_query.get_single() {
+        commands.entity(bird_shop).despawn_recursive();
+    }
+}
+
+fn skin_button_text(skin: &Skin, skins: &SkinCollection, locale: &Locale) -> String {
+    if skins.is_equipped(skin) {
+        locale.text(TextKey::SkinEquipped).to_string()
+    } else if skins.is_unlocked(skin) {
+        locale.text(TextKey::SkinEquip).to_string()
+    } else {
+        format!("{} {}", locale.text(TextKey::SkinBuy), skin.price)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn update_bird_shop(
+    mut commands: Commands,
+    skins: Res<SkinCollection>,
+    wallet: Res<Wallet>,
+    wallet_text_query: Query<&Children, With<WalletText>>,
+    skin_button_query: Query<(Entity, &SkinButton, &Children)>,
+    added_query: Query<(), Added<SkinButton>>,
+    mut text_query: Query<&mut Text>,
+    locale: Res<Locale>,
+) {
+    if !skins.is_changed() && !wallet.is_changed() && !locale.is_changed() && added_query.is_empty()
+    {
+        return;
+    }
+    if let Ok(children) = wallet_text_query.get_single() {
+        for &child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.0 = wallet.coins.to_string();
+            }
+        }
+    }
+    for (entity, skin_button, children) in skin_button_query.iter() {
+        let skin = &SKINS[skin_button.0];
+        // Skins the wallet cannot pay for yet are greyed out
+        if !skins.is_unlocked(skin) && wallet.coins < skin.price {
+            commands.entity(entity).insert(Disabled);
+        } else {
+            commands.entity(entity).remove::<Disabled>();
+        }
+        let button_text = skin_button_text(skin, &skins, &locale);
+        for &child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.0 = button_text.clone();
+            }
+        }
+    }
+}
+
+pub(crate) fn handle_skin_button(
+    In(button): In<Entity>,
+    button_query: Query<&SkinButton>,
+    mut skins: ResMut<SkinCollection>,
+    mut wallet: ResMut<Wallet>,
+) {
+    let Ok(skin_button) = button_query.get(button) else {
+        return;
+    };
+    let skin = &SKINS[skin_button.0];
+    // Pressing the worn skin again should not mark the save as changed
+    if !skins.is_equipped(skin) {
+        skins.buy_or_equip(skin, &mut wallet);
+    }
+}
@@ -0,0 +1,224 @@
+use super::*;
+use std::f32::consts::PI;
+
+/// What a button does when it is pressed, run as a one-off system
+#[derive(Component)]
+pub(crate) struct ButtonAction(Box<ButtonCallback>);
+
+type ButtonCallback = dyn Fn(&mut Commands, Entity) + Send + Sync;
+
+impl ButtonAction {
+    /// Fades over to another screen
+    pub(crate) fn open(state: AppState) -> ButtonAction {
+        ButtonAction(Box::new(move |commands, _| {
+            commands.send_event(StartTransition {
+                to: state.clone(),
+                style: TransitionStyle::Fade,
+            });
+        }))
+    }
+
+    /// Runs `system` every time the button is pressed
+    pub(crate) fn run<M: 'static>(
+        system: impl IntoSystem<(), (), M> + Copy + Send + Sync + 'static,
+    ) -> ButtonAction {
+        ButtonAction(Box::new(move |commands, _| {
+            commands.run_system_cached(system);
+        }))
+    }
+
+    /// Runs `system` with the pressed button, for buttons that carry what they act on
+    pub(crate) fn run_with_button<M: 'static>(
+        system: impl IntoSystem<In<Entity>, (), M> + Copy + Send + Sync + 'static,
+    ) -> ButtonAction {
+        ButtonAction(Box::new(move |commands, button| {
+            commands.run_system_cached_with(system, button);
+        }))
+    }
+}
+
+/// Greys a button out and ignores presses while it is on
+#[derive(Component)]
+pub(crate) struct Disabled;
+
+/// Squashes a button for a moment after it is pressed
+#[derive(Component, Default)]
+pub(crate) struct PressBounce {
+    elapsed: f32,
+}
+
+/// Height a button was spawned with, before it is resized for touch screens
+#[derive(Component)]
+pub(crate) struct ButtonHeight(f32);
+
+/// Set once the screen has been touched, from then on buttons are sized for fingers whichever way the screen is held
+#[derive(Resource, Default)]
+pub(crate) struct TouchScreen(bool);
+
+pub(crate) fn spawn_button(
+    parent: &mut ChildBuilder,
+    text: &str,
+    font: Handle<Font>,
+    button_component: impl Bundle,
+) {
+    spawn_sized_button(
+        parent,
+        text,
+        font,
+        BUTTON_WIDTH_PX,
+        BUTTON_HEIGHT_PX,
+        BUTTON_FONT_SIZE_PX,
+        button_component,
+    );
+}
+
+pub(crate) fn spawn_sized_button(
+    parent: &mut ChildBuilder,
+    text: &str,
+    font: Handle<Font>,
+    width: f32,
+    height: f32,
+    font_size: f32,
+    button_component: impl Bundle,
+) {
+    parent
+        .spawn((
+            Node {
+                width: Val::Px(width),
+                height: Val::Px(height),
+                border: UiRect::all(Val::Px(BUTTON_BORDER_PX)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            BorderColor(Color::srgb_from_array(BUTTON_BORDER_COLOR)),
+            BorderRadius::all(Val::Px(BUTTON_BORDER_RADIUS_PX)),
+            BackgroundColor(Color::srgb_from_array(BUTTON_COLOR)),
+            // Hidden until the button takes the keyboard or gamepad focus
+            Outline::new(
+                Val::Px(FOCUS_RING_WIDTH_PX),
+                Val::Px(FOCUS_RING_OFFSET_PX),
+                Color::NONE,
+            ),
+            Button,
+            ButtonHeight(height),
+            button_component,
+        ))
+        .with_children(|parent| {
+            // Text too long for its button is shrunk to fit rather than wrapped
+            spawn_button_text(parent, text, font, font_size).insert(TextLayout::new_with_no_wrap());
+        });
+}
+
+pub(crate) fn spawn_button_text<'a>(
+    parent: &'a mut ChildBuilder,
+    text: &str,
+    font: Handle<Font>,
+    font_size: f32,
+) -> EntityCommands<'a> {
+    parent.spawn((
+        Text::new(text),
+        TextFont {
+            font,
+            font_size,
+            font_smoothing: FontSmoothing::None,
+        },
+        TextColor::from(Color::srgb_from_array(BUTTON_TEXT_COLOR)),
+    ))
+}
+
+pub(crate) fn press_buttons(
+    mut commands: Commands,
+    button_query: Query<(Entity, &Interaction, &ButtonAction, Has<Disabled>), Changed<Interaction>>,
+    mut play_sound_events: EventWriter<PlaySound>,
+) {
+    for (entity, interaction, action, disabled) in button_query.iter() {
+        if *interaction != Interaction::Pressed || disabled {
+            continue;
+        }
+        play_sound_events.send(PlaySound(SoundEffect::Click));
+        commands.entity(entity).insert(PressBounce::default());
+        (action.0)(&mut commands, entity);
+    }
+}
+
+fn button_colors(interaction: Interaction, disabled: bool) -> ([f32; 3], [f32; 3]) {
+    if disabled {
+        return (BUTTON_DISABLED_COLOR, BUTTON_DISABLED_TEXT_COLOR);
+    }
+    let background = match interaction {
+        Interaction::Pressed => BUTTON_PRESSED_COLOR,
+        Interaction::Hovered => BUTTON_HOVER_COLOR,
+        Interaction::None => BUTTON_COLOR,
+    };
+    (background, BUTTON_TEXT_COLOR)
+}
+
+// Every button that changed is restyled, as the mouse can leave one and enter another in a frame
+#[allow(clippy::type_complexity)]
+pub(crate) fn style_buttons(
+    changed_query: Query<Entity, (With<Button>, Or<(Changed<Interaction>, Added<Disabled>)>)>,
+    mut enabled_buttons: RemovedComponents<Disabled>,
+    mut button_query: Query<(&Interaction, Has<Disabled>, &mut BackgroundColor, &Children)>,
+    mut text_color_query: Query<&mut TextColor>,
+) {
+    for entity in changed_query.iter().chain(enabled_buttons.read()) {
+        let Ok((interaction, disabled, mut background_color, children)) =
+            button_query.get_mut(entity)
+        else {
+            continue;
+        };
+        let (background, text) = button_colors(*interaction, disabled);
+        background_color.0 = Color::srgb_from_array(background);
+        for &child in children.iter() {
+            if let Ok(mut text_color) = text_color_query.get_mut(child) {
+                text_color.0 = Color::srgb_from_array(text);
+            }
+        }
+    }
+}
+
+// Real time so buttons still respond while the game is slowed or frozen
+pub(crate) fn bounce_pressed_buttons(
+    mut commands: Commands,
+    mut button_query: Query<(Entity, &mut PressBounce, &mut Transform)>,
+    time: Res<Time<Real>>,
+) {
+    for (entity, mut bounce, mut transform) in button_query.iter_mut() {
+        bounce.elapsed += time.delta_secs();
+        let progress = (bounce.elapsed / BUTTON_PRESS_DURATION).min(1.0);
+        let squash = (progress * PI).sin() * (1.0 - BUTTON_PRESSED_SCALE);
+        transform.scale = Vec3::splat(1.0 - squash);
+        if progress >= 1.0 {
+            transform.scale = Vec3::ONE;
+            commands.entity(entity).remove::<PressBounce>();
+        }
+    }
+}
+
+pub(crate) fn detect_touch_screen(touches: Res<Touches>, mut touch_screen: ResMut<TouchScreen>) {
+    if !touch_screen.0 && touches.any_just_pressed() {
+        touch_screen.0 = true;
+    }
+}
+
+pub(crate) fn fit_buttons_to_touch(
+    mut button_query: Query<(&mut Node, &ButtonHeight)>,
+    added_query: Query<(), Added<ButtonHeight>>,
+    touch_screen: Res<TouchScreen>,
+) {
+    if !touch_screen.is_changed() && added_query.is_empty() {
+        return;
+    }
+    let scale = if touch_screen.0 {
+        TOUCH_BUTTON_HEIGHT_SCALE
+    } else {
+        1.0
+    };
+    for (mut node, height) in button_query.iter_mut() {
+        let scaled_height = Val::Px(height.0 * scale);
+        if node.height != scaled_height {
+            node.height = scaled_height;
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use super::*;
+
+#[derive(Component)]
+pub(crate) struct CoinDisplay;
+
+#[derive(Component)]
+pub(crate) struct CoinDisplayText;
+
+pub(crate) fn spawn_coin_display(
+    mut commands: Commands,
+    sprites: Res<SpriteAssets>,
+    fonts: Res<FontAssets>,
+) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(SCORE_DISPLAY_TOP_MARGIN_PX),
+                right: Val::Px(COIN_DISPLAY_RIGHT_MARGIN_PX),
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(COIN_DISPLAY_GAP_PX),
+                ..Default::default()
+            },
+            CoinDisplay,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    width: Val::Px(COIN_ICON_SIZE_PX),
+                    height: Val::Px(COIN_ICON_SIZE_PX),
+                    ..Default::default()
+                },
+                ImageNode::new(sprites.coin_sprite.clone()),
+            ));
+            parent
+                .spawn((Node::default(), CoinDisplayText))
+                .with_children(|parent| {
+                    spawn_outlined_text(
+                        parent,
+                        "0",
+                        fonts.title_font.clone(),
+                        COIN_DISPLAY_FONT_SIZE_PX,
+                        SCORE_DISPLAY_COLOR,
+                        SCORE_DISPLAY_OUTLINE_COLOR,
+                        SCORE_DISPLAY_OUTLINE_WIDTH_PX,
+                    );
+                });
+        });
+}
+
+pub(crate) fn despawn_coin_display(
+    mut commands: Commands,
+    coin_display_query: Query<Entity, With<CoinDisplay>>,
+) {
+    if let Ok(coin_display) = coin_display_query.get_single() {
+        commands.entity(coin_display).despawn_recursive();
+    }
+}
+
+pub(crate) fn update_coin_display(
+    run_coins: Res<RunCoins>,
+    coin_text_query: Query<&Children, With<CoinDisplayText>>,
+    mut text_query: Query<&mut Text>,
+) {
+    if let Ok(children) = coin_text_query.get_single() {
+        for &child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.0 = run_coins.0.to_string();
+            }
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use super::*;
+use bevy::asset::LoadedFolder;
+
+#[derive(Component)]
+pub(crate) struct EditorToolbar;
+
+#[derive(Component)]
+pub(crate) struct EditorStatus;
+
+#[derive(Component, Clone, Copy)]
+pub(crate) enum EditorButton {
+    PreviousLevel,
+    NextLevel,
+    ShrinkGap,
+    GrowGap,
+    Playtest,
+    Save,
+    Back,
+}
+
+pub(crate) fn spawn_editor_toolbar(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    locale: Res<Locale>,
+) {
+    commands
+        .spawn((
+            Node {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::SpaceBetween,
+                padding: UiRect::all(Val::Px(EDITOR_TOOLBAR_PADDING_PX)),
+                ..Default::default()
+            },
+            EditorToolbar,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((Node::default(), EditorStatus))
+                .with_children(|parent| {
+                    spawn_outlined_text(
+                        parent,
+                        "",
+                        fonts.button_font.clone(),
+                        EDITOR_STATUS_FONT_SIZE_PX,
+                        TITLE_COLOR,
+                        TITLE_OUTLINE_COLOR,
+                        EDITOR_STATUS_OUTLINE_WIDTH_PX,
+                    );
+                });
+            parent
+                .spawn(Node {
+                    display: Display::Flex,
+                    column_gap: Val::Px(EDITOR_TOOLBAR_GAP_PX),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    for (text, button) in [
+                        (EDITOR_PREVIOUS_TEXT, EditorButton::PreviousLevel),
+                        (EDITOR_NEXT_TEXT, EditorButton::NextLevel),
+                        (EDITOR_SHRINK_TEXT, EditorButton::ShrinkGap),
+                        (EDITOR_GROW_TEXT, EditorButton::GrowGap),
+                        (locale.text(TextKey::Playtest), EditorButton::Playtest),
+                        (locale.text(TextKey::Save), EditorButton::Save),
+                        (locale.text(TextKey::Back), EditorButton::Back),
+                    ] {
+                        spawn_sized_button(
+                            parent,
+                            text,
+                            fonts.button_font.clone(),
+                            EDITOR_BUTTON_WIDTH_PX,
+                            EDITOR_BUTTON_HEIGHT_PX,
+                            EDITOR_BUTTON_FONT_SIZE_PX,
+                            (button, ButtonAction::run_with_button(handle_editor_button)),
+                        );
+                    }
+                });
+        });
+}
+
+pub(crate) fn despawn_editor_toolbar(
+    mut commands: Commands,
+    toolbar_query: Query<Entity, With<EditorToolbar>>,
+) {
+    if let Ok(toolbar) = toolbar_query.get_single() {
+        commands.entity(toolbar).despawn_recursive();
+    }
+}
+
+pub(crate) fn update_editor_status(
+    course: Res<EditorCourse>,
+    mut status_query: Query<&Children, With<EditorStatus>>,
+    mut text_query: Query<&mut Text>,
+    locale: Res<Locale>,
+) {
+    let status = match course.notice {
+        Some(notice) => locale.text(notice.text_key()).to_string(),
+        None => format!(
+            "{}. {}{}",
+            course.slot + 1,
+            course.name,
+            if course.unsaved {
+                EDITOR_UNSAVED_MARK
+            } else {
+                ""
+            }
+        ),
+    };
+    if let Ok(children) = status_query.get_single_mut() {
+        for &child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.0 = status.clone();
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn handle_editor_button(
+    In(button): In<Entity>,
+    button_query: Query<&EditorButton>,
+    mut course: ResMut<EditorCourse>,
+    mut levels: ResMut<Assets<Level>>,
+    level_assets: Res<LevelAssets>,
+    folders: Res<Assets<LoadedFolder>>,
+    mut game_mode: ResMut<GameMode>,
+    mut transition_events: EventWriter<StartTransition>,
+) {
+    let Ok(button) = button_query.get(button) else {
+        return;
+    };
+    course.notice = None;
+    match button {
+        EditorButton::PreviousLevel | EditorButton::NextLevel => {
+            let folder_levels = folders
+                .get(&level_assets.folder)
+                .map(folder_levels)
+                .unwrap_or_default();
+            // One slot past the saved levels holds a blank level
+            let slots = folder_levels.len() + 1;
+            let slot = match button {
+                EditorButton::PreviousLevel => (course.slot + slots - 1) % slots,
+                _ => (course.slot + 1) % slots,
+            };
+            *course = EditorCourse::open(slot, &folder_levels, &levels);
+        }
+        EditorButton::ShrinkGap => resize_selected_gap(&mut course, false),
+        EditorButton::GrowGap => resize_selected_gap(&mut course, true),
+        EditorButton::Playtest => match course.playable_level() {
+            Ok(level) => {
+                *game_mode = GameMode::Playtest {
+                    level: levels.add(level),
+                    start_distance: course.scroll,
+                };
+                transition_events.send(StartTransition {
+                    to: AppState::Playing,
+                    style: TransitionStyle::Wipe,
+                });
+            }
+            Err(error) => course.notice = Some(error),
+        },
+        EditorButton::Save => {
+            course.notice = course.save(&mut levels).err();
+        }
+        EditorButton::Back => {
+            transition_events.send(StartTransition {
+                to: AppState::MainMenu,
+                style: TransitionStyle::Fade,
+            });
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use super::*;
+use bevy::asset::LoadedFolder;
+
+#[derive(Component)]
+pub(crate) struct LevelSelect;
+
+#[derive(Component)]
+pub(crate) struct LevelGrid;
+
+#[derive(Component)]
+pub(crate) struct LevelButton(ActiveLevel);
+
+pub(crate) fn spawn_level_select(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    locale: Res<Locale>,
+) {
+    commands
+        .spawn((
+            Node {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(LEVEL_SELECT_ROW_GAP_PX),
+                padding: UiRect::top(Val::Px(MENU_TOP_PADDING_PX)),
+                ..Default::default()
+            },
+            LevelSelect,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        justify_self: JustifySelf::Center,
+                        top: Val::Px(TITLE_TOP_MARGIN_PX),
+                        ..Default::default()
+                    },
+                    LocalizedText(TextKey::Levels),
+                ))
+                .with_children(|parent| {
+                    spawn_outlined_text(
+                        parent,
+                        locale.text(TextKey::Levels),
+                        fonts.title_font.clone(),
+                        TITLE_FONT_SIZE_PX,
+                        TITLE_COLOR,
+                        TITLE_OUTLINE_COLOR,
+                        TITLE_OUTLINE_WIDTH_PX,
+                    );
+                });
+            parent.spawn((
+                Node {
+                    display: Display::Flex,
+                    flex_wrap: FlexWrap::Wrap,
+                    justify_content: JustifyContent::Center,
+                    width: Val::Px(LEVEL_GRID_WIDTH_PX),
+                    column_gap: Val::Px(LEVEL_GRID_GAP_PX),
+                    row_gap: Val::Px(LEVEL_GRID_GAP_PX),
+                    ..Default::default()
+                },
+                LevelGrid,
+            ));
+            spawn_button(
+                parent,
+                locale.text(TextKey::Back),
+                fonts.button_font.clone(),
+                (
+                    LocalizedText(TextKey::Back),
+                    ButtonAction::open(AppState::MainMenu),
+                    CancelButton,
+                ),
+            );
+        });
+}
+
+pub(crate) fn despawn_level_select(
+    mut commands: Commands,
+    level_select_query: Query<Entity, With<LevelSelect>>,
+) {
+    if let Ok(level_select) = level_select_query.get_single() {
+        commands.entity(level_select).despawn_recursive();
+    }
+}
+
+// The folder may still be loading when the menu opens, so the grid fills in once it is ready
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn populate_level_grid(
+    mut commands: Commands,
+    grid_query: Query<(Entity, Option<&Children>), With<LevelGrid>>,
+    level_assets: Res<LevelAssets>,
+    folders: Res<Assets<LoadedFolder>>,
+    asset_server: Res<AssetServer>,
+    progress: Res<LevelProgress>,
+    fonts: Res<FontAssets>,
+    locale: Res<Locale>,
+) {
+    let Ok((grid, children)) = grid_query.get_single() else {
+        return;
+    };
+    if children.is_some_and(|children| !children.is_empty()) {
+        return;
+    }
+
+    let levels = folders
+        .get(&level_assets.folder)
+        .map(folder_levels)
+        .unwrap_or_default();
+
+    let folder_failed = asset_server.load_state(&level_assets.folder).is_failed();
+    if levels.is_empty() && !folder_failed {
+        return;
+    }
+
+    commands.entity(grid).with_children(|parent| {
+        if levels.is_empty() {
+            spawn_outlined_text(
+                parent,
+                locale.text(TextKey::NoLevels),
+                fonts.button_font.clone(),
+                LEVEL_STARS_FONT_SIZE_PX,
+                TITLE_COLOR,
+                TITLE_OUTLINE_COLOR,
+                LEVEL_STARS_OUTLINE_WIDTH_PX,
+            );
+        }
+        for (index, level) in levels.into_iter().enumerate() {
+            let record = progress.record(&level.key);
+            parent
+                .spawn(Node {
+                    display: Display::Flex,
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    spawn_sized_button(
+                        parent,
+                        &(index + 1).to_string(),
+                        fonts.button_font.clone(),
+                        LEVEL_BUTTON_SIZE_PX,
+                        LEVEL_BUTTON_SIZE_PX,
+                        LEVEL_BUTTON_FONT_SIZE_PX,
+                        (
+                            LevelButton(level),
+                            ButtonAction::run_with_button(handle_level_button),
+                        ),
+                    );
+                    parent.spawn(Node::default()).with_children(|parent| {
+                        spawn_outlined_text(
+                            parent,
+                            &format!(
+                                "{}{}",
+                                star_text(record.stars),
+                                if record.stars_assisted {
+                                    ASSISTED_MARK
+                                } else {
+                                    ""
+                                }
+                            ),
+                            fonts.button_font.clone(),
+                            LEVEL_STARS_FONT_SIZE_PX,
+                            TITLE_COLOR,
+                            TITLE_OUTLINE_COLOR,
+                            LEVEL_STARS_OUTLINE_WIDTH_PX,
+                        );
+                    });
+                });
+        }
+    });
+}
+
+pub(crate) fn star_text(stars: u8) -> String {
+    format!("{stars}/{MAX_LEVEL_STARS}")
+}
+
+pub(crate) fn handle_level_button(
+    In(button): In<Entity>,
+    button_query: Query<&LevelButton>,
+    mut game_mode: ResMut<GameMode>,
+    mut transition_events: EventWriter<StartTransition>,
+) {
+    if let Ok(level_button) = button_query.get(button) {
+        *game_mode = GameMode::Level(level_button.0.clone());
+        transition_events.send(StartTransition {
+            to: AppState::Playing,
+            style: TransitionStyle::Wipe,
+        });
+    }
+}
@@ -0,0 +1,226 @@
+use super::*;
+use std::io::ErrorKind;
+
+#[derive(Component)]
+pub(crate) struct Lobby;
+
+#[derive(Component)]
+pub(crate) struct LobbyStatus;
+
+#[derive(Component)]
+pub(crate) struct HostButton;
+
+#[derive(Component)]
+pub(crate) struct JoinButton;
+
+#[derive(Component)]
+pub(crate) struct StartButton;
+
+pub(crate) fn spawn_lobby_menu(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    locale: Res<Locale>,
+) {
+    commands
+        .spawn((
+            Node {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(LOBBY_ROW_GAP_PX),
+                padding: UiRect::top(Val::Px(MENU_TOP_PADDING_PX)),
+                ..Default::default()
+            },
+            Lobby,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        justify_self: JustifySelf::Center,
+                        top: Val::Px(TITLE_TOP_MARGIN_PX),
+                        ..Default::default()
+                    },
+                    LocalizedText(TextKey::Race),
+                ))
+                .with_children(|parent| {
+                    spawn_outlined_text(
+                        parent,
+                        locale.text(TextKey::Race),
+                        fonts.title_font.clone(),
+                        TITLE_FONT_SIZE_PX,
+                        TITLE_COLOR,
+                        TITLE_OUTLINE_COLOR,
+                        TITLE_OUTLINE_WIDTH_PX,
+                    );
+                });
+            parent
+                .spawn((Node::default(), LobbyStatus))
+                .with_children(|parent| {
+                    spawn_outlined_text(
+                        parent,
+                        "",
+                        fonts.button_font.clone(),
+                        LOBBY_STATUS_FONT_SIZE_PX,
+                        TITLE_COLOR,
+                        TITLE_OUTLINE_COLOR,
+                        LOBBY_STATUS_OUTLINE_WIDTH_PX,
+                    );
+                });
+            spawn_button(
+                parent,
+                locale.text(TextKey::Host),
+                fonts.button_font.clone(),
+                (
+                    LocalizedText(TextKey::Host),
+                    HostButton,
+                    ButtonAction::run(handle_host_button),
+                ),
+            );
+            spawn_button(
+                parent,
+                locale.text(TextKey::Join),
+                fonts.button_font.clone(),
+                (
+                    LocalizedText(TextKey::Join),
+                    JoinButton,
+                    ButtonAction::run(handle_join_button),
+                ),
+            );
+            spawn_button(
+                parent,
+                locale.text(TextKey::Start),
+                fonts.button_font.clone(),
+                (
+                    LocalizedText(TextKey::Start),
+                    StartButton,
+                    ButtonAction::run(handle_start_button),
+                ),
+            );
+            spawn_button(
+                parent,
+                locale.text(TextKey::Back),
+                fonts.button_font.clone(),
+                (
+                    LocalizedText(TextKey::Back),
+                    ButtonAction::open(AppState::MainMenu),
+                    CancelButton,
+                ),
+            );
+        });
+}
+
+pub(crate) fn despawn_lobby_menu(mut commands: Commands, lobby_query: Query<Entity, With<Lobby>>) {
+    if let Ok(lobby) = lobby_query.get_single() {
+        commands.entity(lobby).despawn_recursive();
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_lobby_menu(
+    session: Option<Res<RaceSession>>,
+    notice: Res<RaceNotice>,
+    mut button_query: Query<(
+        &mut Node,
+        Has<HostButton>,
+        Has<JoinButton>,
+        Has<StartButton>,
+    )>,
+    mut status_query: Query<&Children, With<LobbyStatus>>,
+    mut text_query: Query<&mut Text>,
+    locale: Res<Locale>,
+) {
+    let status = match session.as_deref() {
+        None => locale
+            .text(notice.0.unwrap_or(TextKey::LobbyIdle))
+            .to_string(),
+        Some(session) if session.is_host() => {
+            format!(
+                "{} {}",
+                session.racer_count(),
+                locale.text(TextKey::LobbyRacers)
+            )
+        }
+        Some(session) if session.is_connected() => locale.text(TextKey::LobbyWaiting).to_string(),
+        Some(_) => locale.text(TextKey::LobbySearching).to_string(),
+    };
+    if let Ok(children) = status_query.get_single_mut() {
+        for &child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                if text.0 != status {
+                    text.0 = status.clone();
+                }
+            }
+        }
+    }
+
+    let hosting = session.as_deref().is_some_and(RaceSession::is_host);
+    for (mut node, host_button, join_button, start_button) in button_query.iter_mut() {
+        let visible = if host_button || join_button {
+            session.is_none()
+        } else if start_button {
+            hosting
+        } else {
+            continue;
+        };
+        let display = if visible {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        if node.display != display {
+            node.display = display;
+        }
+    }
+}
+
+pub(crate) fn handle_race_button(
+    mut game_mode: ResMut<GameMode>,
+    mut transition_events: EventWriter<StartTransition>,
+) {
+    *game_mode = GameMode::Endless;
+    transition_events.send(StartTransition {
+        to: AppState::Lobby,
+        style: TransitionStyle::Fade,
+    });
+}
+
+pub(crate) fn handle_host_button(mut commands: Commands, mut notice: ResMut<RaceNotice>) {
+    match RaceSession::host() {
+        Ok(session) => {
+            notice.0 = None;
+            commands.insert_resource(session);
+        }
+        Err(error) if error.kind() == ErrorKind::AddrInUse => notice.0 = Some(TextKey::PortInUse),
+        Err(_) => notice.0 = Some(TextKey::Unavailable),
+    }
+}
+
+pub(crate) fn handle_join_button(mut commands: Commands, mut notice: ResMut<RaceNotice>) {
+    match RaceSession::join() {
+        Ok(session) => {
+            notice.0 = None;
+            commands.insert_resource(session);
+        }
+        Err(_) => notice.0 = Some(TextKey::Unavailable),
+    }
+}
+
+pub(crate) fn handle_start_button(
+    session: Option<ResMut<RaceSession>>,
+    mut run_seed: ResMut<RunSeed>,
+    mut transition_events: EventWriter<StartTransition>,
+) {
+    if let Some(mut session) = session {
+        run_seed.0 = rand::random();
+        session.start(run_seed.0);
+        transition_events.send(StartTransition {
+            to: AppState::Playing,
+            style: TransitionStyle::Wipe,
+        });
+    }
+}
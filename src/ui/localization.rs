@@ -0,0 +1,94 @@
+use super::*;
+use bevy::text::TextLayoutInfo;
+
+/// Fills this entity's text children with the text for the key in the current language
+#[derive(Component, Clone, Copy)]
+pub(crate) struct LocalizedText(pub(crate) TextKey);
+
+/// Font and size a text was spawned with, kept while the fallback font or fitting changes them
+#[derive(Component)]
+pub(crate) struct SpawnedFont {
+    font: Handle<Font>,
+    font_size: f32,
+}
+
+pub(crate) fn retext_localized_ui(
+    localized_query: Query<(&LocalizedText, &Children)>,
+    mut text_query: Query<&mut Text>,
+    locale: Res<Locale>,
+) {
+    for (localized, children) in localized_query.iter() {
+        let localized_text = locale.text(localized.0);
+        for &child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                if text.0 != localized_text {
+                    text.0 = localized_text.to_string();
+                }
+            }
+        }
+    }
+}
+
+/// Swaps text over to the fallback font when its pixel font is missing any of its letters,
+/// starting over from the spawned font each time the text changes
+pub(crate) fn apply_font_fallback(
+    mut commands: Commands,
+    mut text_query: Query<(Entity, &Text, &mut TextFont, Option<&SpawnedFont>), Changed<Text>>,
+    fonts: Res<FontAssets>,
+) {
+    for (entity, text, mut text_font, spawned) in text_query.iter_mut() {
+        let (font, font_size) = match spawned {
+            Some(spawned) => (spawned.font.clone(), spawned.font_size),
+            None => (text_font.font.clone(), text_font.font_size),
+        };
+        let (shown_font, shown_font_size) = match fonts.fallback_scale(&font, &text.0) {
+            Some(scale) => (fonts.fallback_font.clone(), font_size * scale),
+            None => (font.clone(), font_size),
+        };
+        if text_font.font == shown_font && text_font.font_size == shown_font_size {
+            continue;
+        }
+        if spawned.is_none() {
+            commands
+                .entity(entity)
+                .insert(SpawnedFont { font, font_size });
+        }
+        text_font.font = shown_font;
+        text_font.font_size = shown_font_size;
+    }
+}
+
+/// Shrinks button text that came out wider than its button, as some translations do
+#[allow(clippy::type_complexity)]
+pub(crate) fn fit_button_text(
+    mut commands: Commands,
+    mut text_query: Query<
+        (
+            Entity,
+            &Parent,
+            &TextLayoutInfo,
+            &mut TextFont,
+            Has<SpawnedFont>,
+        ),
+        Changed<TextLayoutInfo>,
+    >,
+    button_query: Query<&ComputedNode, With<Button>>,
+) {
+    for (entity, parent, layout, mut text_font, has_spawned_font) in text_query.iter_mut() {
+        let Ok(button_node) = button_query.get(parent.get()) else {
+            continue;
+        };
+        let border = button_node.border();
+        let available_width = button_node.size().x - border.left - border.right;
+        if layout.size.x <= available_width || layout.size.x <= 0.0 {
+            continue;
+        }
+        if !has_spawned_font {
+            commands.entity(entity).insert(SpawnedFont {
+                font: text_font.font.clone(),
+                font_size: text_font.font_size,
+            });
+        }
+        text_font.font_size *= available_width / layout.size.x;
+    }
+}
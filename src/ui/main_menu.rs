@@ -1,25 +1,9 @@
 use super::*;
-use bevy::audio::{PlaybackMode, Volume};
 
 #[derive(Component)]
 pub(crate) struct MainMenu;
 
-#[derive(Component)]
-pub(crate) struct PlayButton;
-
-pub(crate) fn spawn_main_menu(
-    mut commands: Commands,
-    fonts: Res<FontAssets>,
-    audio: Res<AudioAssets>,
-) {
-    commands.spawn((
-        AudioPlayer::new(audio.music.clone()),
-        PlaybackSettings {
-            volume: Volume::new(MUSIC_VOLUME),
-            mode: PlaybackMode::Loop,
-            ..Default::default()
-        },
-    ));
+pub(crate) fn spawn_main_menu(mut commands: Commands, fonts: Res<FontAssets>, locale: Res<Locale>) {
     commands
         .spawn((
             Node {
@@ -29,22 +13,27 @@ pub(crate) fn spawn_main_menu(
                 height: Val::Percent(100.0),
                 align_items: AlignItems::Center,
                 justify_content: JustifyContent::Center,
+                row_gap: Val::Px(MAIN_MENU_ROW_GAP_PX),
+                padding: UiRect::top(Val::Px(MENU_TOP_PADDING_PX)),
                 ..Default::default()
             },
             MainMenu,
         ))
         .with_children(|parent| {
             parent
-                .spawn((Node {
-                    position_type: PositionType::Absolute,
-                    justify_self: JustifySelf::Center,
-                    top: Val::Px(TITLE_TOP_MARGIN_PX),
-                    ..Default::default()
-                },))
+                .spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        justify_self: JustifySelf::Center,
+                        top: Val::Px(TITLE_TOP_MARGIN_PX),
+                        ..Default::default()
+                    },
+                    LocalizedText(TextKey::Title),
+                ))
                 .with_children(|parent| {
                     spawn_outlined_text(
                         parent,
-                        TITLE_TEXT,
+                        locale.text(TextKey::Title),
                         fonts.title_font.clone(),
                         TITLE_FONT_SIZE_PX,
                         TITLE_COLOR,
@@ -54,41 +43,132 @@ pub(crate) fn spawn_main_menu(
                 });
             spawn_button(
                 parent,
-                PLAY_BUTTON_TEXT,
+                locale.text(TextKey::Play),
                 fonts.button_font.clone(),
-                PlayButton,
+                (
+                    LocalizedText(TextKey::Play),
+                    ButtonAction::run(handle_play_button),
+                    SlideIn::nth(0),
+                ),
             );
+            // Browsers cannot list the levels folder, open UDP sockets or write level files
+            if !cfg!(target_arch = "wasm32") {
+                spawn_button(
+                    parent,
+                    locale.text(TextKey::Levels),
+                    fonts.button_font.clone(),
+                    (
+                        LocalizedText(TextKey::Levels),
+                        ButtonAction::open(AppState::LevelSelect),
+                        SlideIn::nth(1),
+                    ),
+                );
+                spawn_button(
+                    parent,
+                    locale.text(TextKey::Race),
+                    fonts.button_font.clone(),
+                    (
+                        LocalizedText(TextKey::Race),
+                        ButtonAction::run(handle_race_button),
+                        SlideIn::nth(2),
+                    ),
+                );
+                spawn_button(
+                    parent,
+                    locale.text(TextKey::Editor),
+                    fonts.button_font.clone(),
+                    (
+                        LocalizedText(TextKey::Editor),
+                        ButtonAction::open(AppState::Editor),
+                        SlideIn::nth(3),
+                    ),
+                );
+            }
+            // Smaller buttons for the screens outside of a run share a single row
+            parent
+                .spawn((
+                    Node {
+                        display: Display::Flex,
+                        column_gap: Val::Px(MAIN_MENU_ROW_GAP_PX),
+                        ..Default::default()
+                    },
+                    SlideIn::nth(4),
+                ))
+                .with_children(|parent| {
+                    spawn_sized_button(
+                        parent,
+                        locale.text(TextKey::Birds),
+                        fonts.button_font.clone(),
+                        SMALL_BUTTON_WIDTH_PX,
+                        SMALL_BUTTON_HEIGHT_PX,
+                        SMALL_BUTTON_FONT_SIZE_PX,
+                        (
+                            LocalizedText(TextKey::Birds),
+                            ButtonAction::open(AppState::Birds),
+                        ),
+                    );
+                    spawn_sized_button(
+                        parent,
+                        locale.text(TextKey::Awards),
+                        fonts.button_font.clone(),
+                        SMALL_BUTTON_WIDTH_PX,
+                        SMALL_BUTTON_HEIGHT_PX,
+                        SMALL_BUTTON_FONT_SIZE_PX,
+                        (
+                            LocalizedText(TextKey::Awards),
+                            ButtonAction::open(AppState::Achievements),
+                        ),
+                    );
+                    spawn_sized_button(
+                        parent,
+                        locale.text(TextKey::Stats),
+                        fonts.button_font.clone(),
+                        SMALL_BUTTON_WIDTH_PX,
+                        SMALL_BUTTON_HEIGHT_PX,
+                        SMALL_BUTTON_FONT_SIZE_PX,
+                        (
+                            LocalizedText(TextKey::Stats),
+                            ButtonAction::open(AppState::Stats),
+                        ),
+                    );
+                    spawn_sized_button(
+                        parent,
+                        locale.text(TextKey::Settings),
+                        fonts.button_font.clone(),
+                        SMALL_BUTTON_WIDTH_PX,
+                        SMALL_BUTTON_HEIGHT_PX,
+                        SMALL_BUTTON_FONT_SIZE_PX,
+                        (
+                            LocalizedText(TextKey::Settings),
+                            ButtonAction::open(AppState::Settings),
+                        ),
+                    );
+                });
         });
 }
 
 pub(crate) fn despawn_main_menu(
     mut commands: Commands,
     main_menu_query: Query<Entity, With<MainMenu>>,
-    audio_player_query: Query<Entity, With<AudioPlayer>>,
-    audio: Res<AudioAssets>,
+    transition: Res<ScreenTransition>,
+    mut play_sound_events: EventWriter<PlaySound>,
 ) {
     if let Ok(main_menu) = main_menu_query.get_single() {
         commands.entity(main_menu).despawn_recursive();
     }
-    for entity in audio_player_query.iter() {
-        commands.entity(entity).despawn();
+    // Wipes play their own swoosh as they start
+    if !transition.is_wiping() {
+        play_sound_events.send(PlaySound(SoundEffect::Swoosh));
     }
-    commands.spawn((
-        AudioPlayer::new(audio.swoosh_sound.clone()),
-        PlaybackSettings {
-            volume: Volume::new(SWOOSH_SOUND_VOLUME),
-            ..Default::default()
-        },
-    ));
 }
 
 pub(crate) fn handle_play_button(
-    mut button_query: Query<&Interaction, (Changed<Interaction>, With<PlayButton>)>,
-    mut next_app_state: ResMut<NextState<AppState>>,
+    mut game_mode: ResMut<GameMode>,
+    mut transition_events: EventWriter<StartTransition>,
 ) {
-    if let Ok(interaction) = button_query.get_single_mut() {
-        if let Interaction::Pressed = interaction {
-            next_app_state.set(AppState::Playing)
-        }
-    }
+    *game_mode = GameMode::Endless;
+    transition_events.send(StartTransition {
+        to: AppState::Playing,
+        style: TransitionStyle::Wipe,
+    });
 }
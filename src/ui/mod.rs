@@ -1,14 +1,43 @@
 use super::*;
 use bevy::text::FontSmoothing;
+use bevy::ui::UiSystem;
 
+mod achievements_menu;
+mod bird_shop;
+mod button;
+mod coin_display;
+mod editor_toolbar;
+mod level_select;
+mod lobby;
+mod localization;
 mod main_menu;
+mod navigation;
+mod power_up_display;
 mod retry_menu;
 mod score;
+mod settings_menu;
+mod stats_menu;
+mod toasts;
+mod transition;
 
 // Re-exports
+pub(crate) use achievements_menu::*;
+pub(crate) use bird_shop::*;
+pub(crate) use button::*;
+pub(crate) use coin_display::*;
+pub(crate) use editor_toolbar::*;
+pub(crate) use level_select::*;
+pub(crate) use lobby::*;
+pub(crate) use localization::*;
 pub(crate) use main_menu::*;
+pub(crate) use navigation::*;
+pub(crate) use power_up_display::*;
 pub(crate) use retry_menu::*;
 pub(crate) use score::*;
+pub(crate) use settings_menu::*;
+pub(crate) use stats_menu::*;
+pub(crate) use toasts::*;
+pub(crate) use transition::*;
 
 // Outlined Text
 const OUTLINE_OFFSETS: [[f32; 2]; 4] = [[0.0, 1.0], [0.0, -1.0], [1.0, 0.0], [-1.0, 0.0]];
@@ -16,6 +45,9 @@ const OUTLINE_OFFSETS: [[f32; 2]; 4] = [[0.0, 1.0], [0.0, -1.0], [1.0, 0.0], [-1
 // General Buttons
 const BUTTON_COLOR: [f32; 3] = [0.984, 0.949, 0.212]; // #fbf236
 const BUTTON_HOVER_COLOR: [f32; 3] = [0.902, 0.867, 0.2]; // #e6dd33
+const BUTTON_PRESSED_COLOR: [f32; 3] = [0.8, 0.769, 0.176]; // #ccc42d
+const BUTTON_DISABLED_COLOR: [f32; 3] = [0.608, 0.678, 0.718]; // #9badb7
+const BUTTON_DISABLED_TEXT_COLOR: [f32; 3] = [0.349, 0.337, 0.322]; // #595652
 const BUTTON_BORDER_COLOR: [f32; 3] = [0.0, 0.0, 0.0]; // #000000
 const BUTTON_TEXT_COLOR: [f32; 3] = [0.0, 0.0, 0.0]; // #000000
 const BUTTON_WIDTH_PX: f32 = 200.0;
@@ -23,21 +55,113 @@ const BUTTON_HEIGHT_PX: f32 = 70.0;
 const BUTTON_BORDER_PX: f32 = 2.5;
 const BUTTON_BORDER_RADIUS_PX: f32 = 10.0;
 const BUTTON_FONT_SIZE_PX: f32 = 75.0;
+/// Smallest size a button shrinks to, halfway through its press bounce
+const BUTTON_PRESSED_SCALE: f32 = 0.92;
+const BUTTON_PRESS_DURATION: f32 = 0.15;
+/// Buttons grow taller once the screen has been touched so they are easier to hit with a finger
+const TOUCH_BUTTON_HEIGHT_SCALE: f32 = 1.3;
+
+// Menus
+const MENU_TOP_PADDING_PX: f32 = 120.0;
 
 // Main Menu
-const TITLE_TEXT: &str = "FlappyBird";
 const TITLE_FONT_SIZE_PX: f32 = 75.0;
 const TITLE_TOP_MARGIN_PX: f32 = 85.0;
 const TITLE_OUTLINE_WIDTH_PX: f32 = 3.5;
 const TITLE_COLOR: [f32; 3] = [1.0, 1.0, 1.0]; // #FFFFFF
 const TITLE_OUTLINE_COLOR: [f32; 3] = [0.0, 0.0, 0.0]; // #000000
 
-const PLAY_BUTTON_TEXT: &str = "Play";
+const MAIN_MENU_ROW_GAP_PX: f32 = 8.0;
+const SMALL_BUTTON_WIDTH_PX: f32 = 115.0;
+const SMALL_BUTTON_HEIGHT_PX: f32 = 50.0;
+const SMALL_BUTTON_FONT_SIZE_PX: f32 = 42.0;
+
+// Editor
+const EDITOR_TOOLBAR_PADDING_PX: f32 = 12.0;
+const EDITOR_TOOLBAR_GAP_PX: f32 = 6.0;
+const EDITOR_BUTTON_WIDTH_PX: f32 = 64.0;
+const EDITOR_BUTTON_HEIGHT_PX: f32 = 44.0;
+const EDITOR_BUTTON_FONT_SIZE_PX: f32 = 40.0;
+const EDITOR_STATUS_FONT_SIZE_PX: f32 = 30.0;
+const EDITOR_STATUS_OUTLINE_WIDTH_PX: f32 = 2.0;
+const EDITOR_PREVIOUS_TEXT: &str = "<";
+const EDITOR_NEXT_TEXT: &str = ">";
+const EDITOR_SHRINK_TEXT: &str = "-";
+const EDITOR_GROW_TEXT: &str = "+";
+const EDITOR_UNSAVED_MARK: &str = "*";
+
+// Bird Shop
+const BIRD_SHOP_ROW_GAP_PX: f32 = 8.0;
+const SKIN_ROW_GAP_PX: f32 = 10.0;
+const SKIN_BIRD_PREVIEW_SCALE: f32 = 3.0;
+const SKIN_PIPE_PREVIEW_SCALE: f32 = 2.0;
+const SKIN_PIPE_PREVIEW_HEIGHT_PX: f32 = 12.0;
+const SKIN_NAME_WIDTH_PX: f32 = 150.0;
+const SKIN_NAME_FONT_SIZE_PX: f32 = 36.0;
+const SKIN_NAME_OUTLINE_WIDTH_PX: f32 = 2.0;
+const SKIN_BUTTON_WIDTH_PX: f32 = 130.0;
+const SKIN_BUTTON_HEIGHT_PX: f32 = 44.0;
+const SKIN_BUTTON_FONT_SIZE_PX: f32 = 45.0;
+
+// Achievements
+const ACHIEVEMENT_ROW_GAP_PX: f32 = 8.0;
+const ACHIEVEMENT_NAME_WIDTH_PX: f32 = 140.0;
+const ACHIEVEMENT_DESCRIPTION_WIDTH_PX: f32 = 200.0;
+const ACHIEVEMENT_STATUS_WIDTH_PX: f32 = 100.0;
+const ACHIEVEMENT_NAME_FONT_SIZE_PX: f32 = 28.0;
+const ACHIEVEMENT_DESCRIPTION_FONT_SIZE_PX: f32 = 20.0;
+const ACHIEVEMENT_OUTLINE_WIDTH_PX: f32 = 1.5;
+const ACHIEVEMENT_LOCKED_COLOR: [f32; 3] = [0.6, 0.6, 0.6]; // #999999
+
+// Stats
+const STATS_ROW_GAP_PX: f32 = 6.0;
+const STATS_COLUMN_GAP_PX: f32 = 30.0;
+const STATS_FONT_SIZE_PX: f32 = 22.0;
+const STATS_OUTLINE_WIDTH_PX: f32 = 1.5;
+const STATS_LABEL_WIDTH_PX: f32 = 85.0;
+const STATS_VALUE_WIDTH_PX: f32 = 70.0;
+const STATS_BAR_GAP_PX: f32 = 6.0;
+const STATS_DEATH_BAR_WIDTH_PX: f32 = 80.0;
+const STATS_DEATH_BAR_HEIGHT_PX: f32 = 10.0;
+const STATS_CHART_WIDTH_PX: f32 = 404.0;
+const STATS_CHART_HEIGHT_PX: f32 = 70.0;
+const STATS_CHART_BAR_WIDTH_PX: f32 = 6.0;
+const STATS_CHART_BAR_GAP_PX: f32 = 2.0;
+const STATS_CHART_BAR_MAX_HEIGHT_PX: f32 = STATS_CHART_HEIGHT_PX - 2.0 * STATS_CHART_BAR_GAP_PX;
+const STATS_CHART_BAR_MIN_HEIGHT_PX: f32 = 2.0;
+const STATS_CHART_BACKGROUND_ALPHA: f32 = 0.35;
+const STATS_BUTTON_GAP_PX: f32 = 8.0;
+
+// Settings
+const SETTINGS_ROW_GAP_PX: f32 = 8.0;
+const SETTING_LABEL_WIDTH_PX: f32 = 170.0;
+const SETTING_LABEL_FONT_SIZE_PX: f32 = 36.0;
+const SETTING_LABEL_OUTLINE_WIDTH_PX: f32 = 2.0;
+const SETTING_BUTTON_WIDTH_PX: f32 = 150.0;
+const SETTING_BUTTON_HEIGHT_PX: f32 = 44.0;
+const SETTING_BUTTON_FONT_SIZE_PX: f32 = 40.0;
+
+// Level Select
+const LEVEL_SELECT_ROW_GAP_PX: f32 = 16.0;
+const LEVEL_GRID_WIDTH_PX: f32 = 420.0;
+const LEVEL_GRID_GAP_PX: f32 = 12.0;
+const LEVEL_BUTTON_SIZE_PX: f32 = 70.0;
+const LEVEL_BUTTON_FONT_SIZE_PX: f32 = 60.0;
+const LEVEL_STARS_FONT_SIZE_PX: f32 = 30.0;
+const LEVEL_STARS_OUTLINE_WIDTH_PX: f32 = 2.0;
+const MAX_LEVEL_STARS: u8 = 3;
+
+// Lobby
+const LOBBY_ROW_GAP_PX: f32 = 8.0;
+const LOBBY_STATUS_FONT_SIZE_PX: f32 = 40.0;
+const LOBBY_STATUS_OUTLINE_WIDTH_PX: f32 = 2.0;
 
 // Retry Menu
 const RETRY_MENU_ROW_GAP_PX: f32 = 8.0;
-const RETRY_BUTTON_TEXT: &str = "Retry";
-const MAIN_MENU_BUTTON_TEXT: &str = "Menu";
+const RETRY_RESULT_FONT_SIZE_PX: f32 = 50.0;
+const RETRY_RESULT_OUTLINE_WIDTH_PX: f32 = 2.5;
+/// Follows a best score set with assists on
+const ASSISTED_MARK: &str = "*";
 
 // Score Display
 const SCORE_DISPLAY_FONT_SIZE_PX: f32 = 50.0;
@@ -46,22 +170,144 @@ const SCORE_DISPLAY_OUTLINE_WIDTH_PX: f32 = 2.5;
 const SCORE_DISPLAY_COLOR: [f32; 3] = [1.0, 1.0, 1.0]; // #FFFFFF
 const SCORE_DISPLAY_OUTLINE_COLOR: [f32; 3] = [0.0, 0.0, 0.0]; // #000000
 
-const SCORE_TIMER_INTERVAL: f32 = (PIPE_SPACING * SPRITE_SCALE) / PIPE_SPEED;
+// Coin Display
+const COIN_DISPLAY_RIGHT_MARGIN_PX: f32 = 16.0;
+const COIN_DISPLAY_GAP_PX: f32 = 6.0;
+const COIN_ICON_SIZE_PX: f32 = 24.0;
+const COIN_DISPLAY_FONT_SIZE_PX: f32 = 36.0;
+
+// Toasts
+const TOAST_TOP_MARGIN_PX: f32 = 16.0;
+const TOAST_RIGHT_MARGIN_PX: f32 = 16.0;
+const TOAST_SPACING_PX: f32 = 72.0;
+const TOAST_WIDTH_PX: f32 = 240.0;
+const TOAST_PADDING_PX: f32 = 6.0;
+const TOAST_HEADER_FONT_SIZE_PX: f32 = 26.0;
+const TOAST_NAME_FONT_SIZE_PX: f32 = 36.0;
+const TOAST_Z_INDEX: i32 = 10;
+const TOAST_SLIDE_DURATION: f32 = 0.35;
+const TOAST_HOLD_DURATION: f32 = 2.5;
+
+// Menu Navigation
+const FOCUS_UP_KEYS: [KeyCode; 2] = [KeyCode::ArrowUp, KeyCode::KeyW];
+const FOCUS_DOWN_KEYS: [KeyCode; 2] = [KeyCode::ArrowDown, KeyCode::KeyS];
+const FOCUS_LEFT_KEYS: [KeyCode; 2] = [KeyCode::ArrowLeft, KeyCode::KeyA];
+const FOCUS_RIGHT_KEYS: [KeyCode; 2] = [KeyCode::ArrowRight, KeyCode::KeyD];
+const ACTIVATE_KEYS: [KeyCode; 2] = [KeyCode::Enter, KeyCode::NumpadEnter];
+const CANCEL_KEYS: [KeyCode; 2] = [KeyCode::Escape, KeyCode::Backspace];
+/// Retries straight from the retry menu, the same key that flaps
+const RETRY_KEY: KeyCode = KeyCode::Space;
+const FOCUS_UP_BUTTONS: [GamepadButton; 1] = [GamepadButton::DPadUp];
+const FOCUS_DOWN_BUTTONS: [GamepadButton; 1] = [GamepadButton::DPadDown];
+const FOCUS_LEFT_BUTTONS: [GamepadButton; 1] = [GamepadButton::DPadLeft];
+const FOCUS_RIGHT_BUTTONS: [GamepadButton; 1] = [GamepadButton::DPadRight];
+const ACTIVATE_BUTTONS: [GamepadButton; 2] = [GamepadButton::South, GamepadButton::Start];
+const CANCEL_BUTTONS: [GamepadButton; 1] = [GamepadButton::East];
+/// How much being off to the side counts against a button compared to being further ahead
+const FOCUS_SIDEWAYS_PENALTY: f32 = 2.0;
+const FOCUS_RING_WIDTH_PX: f32 = 3.0;
+const FOCUS_RING_OFFSET_PX: f32 = 2.0;
+
+// Transitions
+const TRANSITION_COLOR: [f32; 3] = [0.0, 0.0, 0.0]; // #000000
+/// Above toasts so nothing shows through while the screen changes
+const TRANSITION_Z_INDEX: i32 = 20;
+const TRANSITION_COVER_DURATION: f32 = 0.3;
+const TRANSITION_REVEAL_DURATION: f32 = 0.3;
+const SLIDE_IN_DISTANCE_PX: f32 = 320.0;
+const SLIDE_IN_DURATION: f32 = 0.35;
+/// Delay between one menu element starting to slide in and the next
+const SLIDE_IN_STAGGER: f32 = 0.06;
+
+// Power-up Display
+const POWER_UP_DISPLAY_MARGIN_PX: f32 = 16.0;
+const POWER_UP_DISPLAY_GAP_PX: f32 = 6.0;
+const POWER_UP_ICON_SIZE_PX: f32 = 32.0;
+const POWER_UP_TIMER_FONT_SIZE_PX: f32 = 40.0;
+const POWER_UP_TIMER_OUTLINE_WIDTH_PX: f32 = 2.0;
 
 pub(crate) struct GameUiPlugin;
 
 impl Plugin for GameUiPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            (
-                handle_play_button,
-                handle_retry_button,
-                handle_main_menu_button,
-                handle_button_hover,
-                handle_score_timer,
-            ),
-        );
+        app.add_event::<ShowToast>()
+            .add_event::<StartTransition>()
+            .init_resource::<SettingsPage>()
+            .init_resource::<ScreenTransition>()
+            .init_resource::<MenuFocus>()
+            .init_resource::<TouchScreen>()
+            .add_systems(Startup, spawn_transition_overlay)
+            // After the UI reads the mouse so keyboard and gamepad presses are not overwritten
+            .add_systems(
+                PreUpdate,
+                (follow_hovered_button, navigate_menus)
+                    .chain()
+                    .after(UiSystem::Focus)
+                    .run_if(not(in_state(AppState::Playing)).and(not(in_state(AppState::Editor)))),
+            )
+            .add_systems(
+                Update,
+                (
+                    (press_buttons, style_buttons).chain(),
+                    bounce_pressed_buttons,
+                    (detect_touch_screen, fit_buttons_to_touch).chain(),
+                    update_score_display.run_if(resource_changed::<Score>),
+                    update_coin_display.run_if(resource_changed::<RunCoins>),
+                    (queue_achievement_toasts, spawn_toasts, animate_toasts).chain(),
+                    (
+                        start_transitions,
+                        advance_transition,
+                        update_transition_overlay,
+                    )
+                        .chain(),
+                    slide_in_menu_elements,
+                    draw_focus_ring.run_if(resource_changed::<MenuFocus>),
+                    retext_localized_ui.run_if(resource_changed::<Locale>),
+                    (apply_font_fallback, fit_button_text)
+                        .chain()
+                        .after(retext_localized_ui),
+                ),
+            )
+            .add_systems(
+                Update,
+                update_lobby_menu
+                    .after(press_buttons)
+                    .run_if(in_state(AppState::Lobby)),
+            )
+            .add_systems(
+                Update,
+                populate_level_grid.run_if(in_state(AppState::LevelSelect)),
+            )
+            .add_systems(
+                Update,
+                update_editor_status
+                    .after(press_buttons)
+                    .run_if(in_state(AppState::Editor)),
+            )
+            .add_systems(
+                Update,
+                update_bird_shop
+                    .after(press_buttons)
+                    .run_if(in_state(AppState::Birds)),
+            )
+            .add_systems(
+                Update,
+                (
+                    update_settings_menu
+                        .run_if(resource_changed::<Settings>.or(resource_changed::<Locale>)),
+                    update_settings_page
+                        .run_if(resource_changed::<SettingsPage>.or(resource_changed::<Locale>)),
+                )
+                    .after(press_buttons)
+                    .run_if(in_state(AppState::Settings)),
+            )
+            .add_systems(OnEnter(AppState::Playing), spawn_power_up_display)
+            .add_systems(OnExit(AppState::Playing), despawn_power_up_display)
+            .add_systems(
+                Update,
+                update_power_up_display
+                    .run_if(in_state(AppState::Playing).and(resource_changed::<ActivePowerUps>)),
+            );
     }
 }
 
@@ -101,59 +347,3 @@ pub(crate) fn spawn_outlined_text(
         TextColor::from(Color::srgb_from_array(text_color)),
     ));
 }
-
-pub(crate) fn spawn_button(
-    parent: &mut ChildBuilder,
-    text: &str,
-    font: Handle<Font>,
-    button_component: impl Bundle,
-) {
-    parent
-        .spawn((
-            Node {
-                width: Val::Px(BUTTON_WIDTH_PX),
-                height: Val::Px(BUTTON_HEIGHT_PX),
-                border: UiRect::all(Val::Px(BUTTON_BORDER_PX)),
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::Center,
-                ..Default::default()
-            },
-            BorderColor(Color::srgb_from_array(BUTTON_BORDER_COLOR)),
-            BorderRadius::all(Val::Px(BUTTON_BORDER_RADIUS_PX)),
-            BackgroundColor(Color::srgb_from_array(BUTTON_COLOR)),
-            Button,
-            button_component,
-        ))
-        .with_children(|parent| spawn_button_text(parent, text, font));
-}
-
-pub(crate) fn spawn_button_text(parent: &mut ChildBuilder, text: &str, font: Handle<Font>) {
-    parent.spawn((
-        Text::new(text),
-        TextFont {
-            font,
-            font_size: BUTTON_FONT_SIZE_PX,
-            font_smoothing: FontSmoothing::None,
-        },
-        TextColor::from(Color::srgb_from_array(BUTTON_TEXT_COLOR)),
-    ));
-}
-
-fn handle_button_hover(
-    mut button_query: Query<
-        (&Interaction, &mut BackgroundColor),
-        (Changed<Interaction>, With<Button>),
-    >,
-) {
-    if let Ok((interaction, mut background_color)) = button_query.get_single_mut() {
-        match interaction {
-            Interaction::Hovered => {
-                background_color.0 = Color::srgb_from_array(BUTTON_HOVER_COLOR);
-            }
-            Interaction::None => {
-                background_color.0 = Color::srgb_from_array(BUTTON_COLOR);
-            }
-            Interaction::Pressed => (),
-        }
-    }
-}
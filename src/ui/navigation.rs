@@ -0,0 +1,206 @@
+use super::*;
+
+/// Pressed by Escape or the gamepad's B button to leave the current menu
+#[derive(Component)]
+pub(crate) struct CancelButton;
+
+/// Button pressed from the keyboard or a gamepad, released again on the next frame
+#[derive(Component)]
+pub(crate) struct NavigationPress;
+
+/// Button that arrow keys and the D-pad move away from, shown with a ring around it
+#[derive(Resource, Default)]
+pub(crate) struct MenuFocus(Option<Entity>);
+
+/// A button that can take the focus right now
+struct FocusableButton {
+    entity: Entity,
+    position: Vec2,
+    cancels: bool,
+    retries: bool,
+}
+
+#[derive(Clone, Copy)]
+enum NavigationInput {
+    Move(Vec2),
+    Activate,
+    Cancel,
+    Retry,
+}
+
+fn read_navigation_input(
+    keys: &ButtonInput<KeyCode>,
+    gamepads: &Query<&Gamepad>,
+    app_state: &AppState,
+) -> Option<NavigationInput> {
+    let gamepad_pressed = |buttons: &[GamepadButton]| {
+        gamepads
+            .iter()
+            .any(|gamepad| gamepad.any_just_pressed(buttons.iter().copied()))
+    };
+    // Alt+Enter is left to the fullscreen toggle
+    let alt_pressed = keys.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]);
+
+    if keys.any_just_pressed(FOCUS_UP_KEYS) || gamepad_pressed(&FOCUS_UP_BUTTONS) {
+        Some(NavigationInput::Move(Vec2::NEG_Y))
+    } else if keys.any_just_pressed(FOCUS_DOWN_KEYS) || gamepad_pressed(&FOCUS_DOWN_BUTTONS) {
+        Some(NavigationInput::Move(Vec2::Y))
+    } else if keys.any_just_pressed(FOCUS_LEFT_KEYS) || gamepad_pressed(&FOCUS_LEFT_BUTTONS) {
+        Some(NavigationInput::Move(Vec2::NEG_X))
+    } else if keys.any_just_pressed(FOCUS_RIGHT_KEYS) || gamepad_pressed(&FOCUS_RIGHT_BUTTONS) {
+        Some(NavigationInput::Move(Vec2::X))
+    } else if (keys.any_just_pressed(ACTIVATE_KEYS) && !alt_pressed)
+        || gamepad_pressed(&ACTIVATE_BUTTONS)
+    {
+        Some(NavigationInput::Activate)
+    } else if keys.any_just_pressed(CANCEL_KEYS) || gamepad_pressed(&CANCEL_BUTTONS) {
+        Some(NavigationInput::Cancel)
+    } else if *app_state == AppState::RetryMenu && keys.just_pressed(RETRY_KEY) {
+        Some(NavigationInput::Retry)
+    } else {
+        None
+    }
+}
+
+/// The button `direction` points to from `from`, favouring ones in line with it
+fn neighbour_in_direction(
+    from: Vec2,
+    direction: Vec2,
+    buttons: &[FocusableButton],
+) -> Option<Entity> {
+    buttons
+        .iter()
+        .filter_map(|button| {
+            let offset = button.position - from;
+            let forward = offset.dot(direction);
+            let sideways = offset.perp_dot(direction).abs();
+            (forward > 0.0).then_some((button.entity, forward + sideways * FOCUS_SIDEWAYS_PENALTY))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(entity, _)| entity)
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) fn navigate_menus(
+    mut commands: Commands,
+    mut button_query: Query<
+        (
+            Entity,
+            &mut Interaction,
+            &GlobalTransform,
+            &ComputedNode,
+            &InheritedVisibility,
+            Has<CancelButton>,
+            Has<RetryButton>,
+            Has<NavigationPress>,
+        ),
+        With<Button>,
+    >,
+    mut focus: ResMut<MenuFocus>,
+    keys: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    app_state: Res<State<AppState>>,
+    transition: Res<ScreenTransition>,
+) {
+    // Let go of last frame's press so the button can be pressed again
+    for (entity, mut interaction, .., pressed) in button_query.iter_mut() {
+        if pressed {
+            *interaction = Interaction::None;
+            commands.entity(entity).remove::<NavigationPress>();
+        }
+    }
+
+    // Buttons hidden on another page or menu cannot be focused
+    let buttons: Vec<FocusableButton> = button_query
+        .iter()
+        .filter(|(_, _, _, node, visibility, ..)| {
+            visibility.get() && node.size().cmpgt(Vec2::ZERO).all()
+        })
+        .map(
+            |(entity, _, transform, _, _, cancels, retries, _)| FocusableButton {
+                entity,
+                position: transform.translation().truncate(),
+                cancels,
+                retries,
+            },
+        )
+        .collect();
+    let focused = focus
+        .0
+        .and_then(|focused| buttons.iter().find(|button| button.entity == focused));
+    if focused.is_none() && focus.0.is_some() {
+        focus.0 = None;
+    }
+
+    if transition.is_active() {
+        return;
+    }
+    let Some(input) = read_navigation_input(&keys, &gamepads, app_state.get()) else {
+        return;
+    };
+    let target = match (input, focused) {
+        // The first press only shows where the focus starts, top left of the menu
+        (NavigationInput::Move(_) | NavigationInput::Activate, None) => {
+            focus.0 = buttons
+                .iter()
+                .min_by(|a, b| {
+                    (a.position.y.total_cmp(&b.position.y))
+                        .then(a.position.x.total_cmp(&b.position.x))
+                })
+                .map(|button| button.entity);
+            None
+        }
+        (NavigationInput::Move(direction), Some(button)) => {
+            if let Some(neighbour) = neighbour_in_direction(button.position, direction, &buttons) {
+                focus.0 = Some(neighbour);
+            }
+            None
+        }
+        (NavigationInput::Activate, Some(button)) => Some(button.entity),
+        (NavigationInput::Cancel, _) => buttons
+            .iter()
+            .find(|button| button.cancels)
+            .map(|button| button.entity),
+        (NavigationInput::Retry, _) => buttons
+            .iter()
+            .find(|button| button.retries)
+            .map(|button| button.entity),
+    };
+
+    // Pressing the button the same way a click does keeps every menu's handler as it is
+    if let Some(target) = target {
+        if let Ok((_, mut interaction, ..)) = button_query.get_mut(target) {
+            *interaction = Interaction::Pressed;
+            commands.entity(target).insert(NavigationPress);
+        }
+    }
+}
+
+/// Moves the focus to whichever button the mouse last hovered
+#[allow(clippy::type_complexity)]
+pub(crate) fn follow_hovered_button(
+    button_query: Query<(Entity, &Interaction), (Changed<Interaction>, With<Button>)>,
+    mut focus: ResMut<MenuFocus>,
+) {
+    for (entity, interaction) in button_query.iter() {
+        if *interaction == Interaction::Hovered {
+            focus.0 = Some(entity);
+        }
+    }
+}
+
+pub(crate) fn draw_focus_ring(
+    mut button_query: Query<(Entity, &mut Outline), With<Button>>,
+    focus: Res<MenuFocus>,
+) {
+    for (entity, mut outline) in button_query.iter_mut() {
+        let color = if focus.0 == Some(entity) {
+            Color::srgb_from_array(BUTTON_HOVER_COLOR)
+        } else {
+            Color::NONE
+        };
+        if outline.color != color {
+            outline.color = color;
+        }
+    }
+}
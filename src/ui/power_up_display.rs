@@ -0,0 +1,106 @@
+use super::*;
+
+#[derive(Component)]
+pub(crate) struct PowerUpDisplay;
+
+/// Row for one power-up, only shown while it is active
+#[derive(Component)]
+pub(crate) struct PowerUpTimer(PowerUpKind);
+
+#[derive(Component)]
+pub(crate) struct PowerUpTimerText(PowerUpKind);
+
+pub(crate) fn spawn_power_up_display(
+    mut commands: Commands,
+    sprites: Res<SpriteAssets>,
+    fonts: Res<FontAssets>,
+) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(POWER_UP_DISPLAY_MARGIN_PX),
+                left: Val::Px(POWER_UP_DISPLAY_MARGIN_PX),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(POWER_UP_DISPLAY_GAP_PX),
+                ..Default::default()
+            },
+            PowerUpDisplay,
+        ))
+        .with_children(|parent| {
+            for kind in PowerUpKind::ALL {
+                parent
+                    .spawn((
+                        Node {
+                            display: Display::None,
+                            align_items: AlignItems::Center,
+                            column_gap: Val::Px(POWER_UP_DISPLAY_GAP_PX),
+                            ..Default::default()
+                        },
+                        PowerUpTimer(kind),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Node {
+                                width: Val::Px(POWER_UP_ICON_SIZE_PX),
+                                height: Val::Px(POWER_UP_ICON_SIZE_PX),
+                                ..Default::default()
+                            },
+                            ImageNode::new(sprites.power_up_sprite(kind).clone()),
+                        ));
+                        parent
+                            .spawn((Node::default(), PowerUpTimerText(kind)))
+                            .with_children(|parent| {
+                                spawn_outlined_text(
+                                    parent,
+                                    "",
+                                    fonts.button_font.clone(),
+                                    POWER_UP_TIMER_FONT_SIZE_PX,
+                                    SCORE_DISPLAY_COLOR,
+                                    SCORE_DISPLAY_OUTLINE_COLOR,
+                                    POWER_UP_TIMER_OUTLINE_WIDTH_PX,
+                                );
+                            });
+                    });
+            }
+        });
+}
+
+pub(crate) fn despawn_power_up_display(
+    mut commands: Commands,
+    power_up_display_query: Query<Entity, With<PowerUpDisplay>>,
+) {
+    if let Ok(power_up_display) = power_up_display_query.get_single() {
+        commands.entity(power_up_display).despawn_recursive();
+    }
+}
+
+pub(crate) fn update_power_up_display(
+    power_ups: Res<ActivePowerUps>,
+    mut timer_query: Query<(&PowerUpTimer, &mut Node)>,
+    timer_text_query: Query<(&PowerUpTimerText, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (timer, mut node) in timer_query.iter_mut() {
+        let display = match power_ups.remaining(timer.0) {
+            Some(_) => Display::Flex,
+            None => Display::None,
+        };
+        if node.display != display {
+            node.display = display;
+        }
+    }
+    for (timer_text, children) in timer_text_query.iter() {
+        let Some(remaining) = power_ups.remaining(timer_text.0) else {
+            continue;
+        };
+        let seconds = remaining.ceil().to_string();
+        for &child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                if text.0 != seconds {
+                    text.0 = seconds.clone();
+                }
+            }
+        }
+    }
+}
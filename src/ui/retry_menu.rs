@@ -3,13 +3,41 @@ use super::*;
 #[derive(Component)]
 pub(crate) struct RetryMenu;
 
+/// Also pressed by the retry key without moving the focus to it
 #[derive(Component)]
 pub(crate) struct RetryButton;
 
-#[derive(Component)]
-pub(crate) struct MainMenuButton;
+pub(crate) fn spawn_retry_menu(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    locale: Res<Locale>,
+    game_mode: Res<GameMode>,
+    outcome: Res<LevelOutcome>,
+    progress: Res<LevelProgress>,
+) {
+    // Playtests return to the editor instead of the main menu
+    let main_menu_button_key = match *game_mode {
+        GameMode::Playtest { .. } => TextKey::Edit,
+        _ => TextKey::MainMenu,
+    };
+    let result_text = match (&*game_mode, outcome.stars) {
+        (GameMode::Level(_) | GameMode::Playtest { .. }, Some(stars)) => Some(format!(
+            "{} {}",
+            locale.text(TextKey::LevelCleared),
+            star_text(stars)
+        )),
+        (GameMode::Level(active_level), None) => {
+            let record = progress.record(&active_level.key);
+            Some(format!(
+                "{} {}{}",
+                locale.text(TextKey::LevelBest),
+                record.best_score,
+                if record.assisted { ASSISTED_MARK } else { "" }
+            ))
+        }
+        (GameMode::Endless | GameMode::Playtest { .. }, _) => None,
+    };
 
-pub(crate) fn spawn_retry_menu(mut commands: Commands, fonts: Res<FontAssets>) {
     commands
         .spawn((
             Node {
@@ -25,17 +53,40 @@ pub(crate) fn spawn_retry_menu(mut commands: Commands, fonts: Res<FontAssets>) {
             RetryMenu,
         ))
         .with_children(|parent| {
+            if let Some(result_text) = result_text {
+                parent.spawn(Node::default()).with_children(|parent| {
+                    spawn_outlined_text(
+                        parent,
+                        &result_text,
+                        fonts.title_font.clone(),
+                        RETRY_RESULT_FONT_SIZE_PX,
+                        TITLE_COLOR,
+                        TITLE_OUTLINE_COLOR,
+                        RETRY_RESULT_OUTLINE_WIDTH_PX,
+                    );
+                });
+            }
             spawn_button(
                 parent,
-                RETRY_BUTTON_TEXT,
+                locale.text(TextKey::Retry),
                 fonts.button_font.clone(),
-                RetryButton,
+                (
+                    LocalizedText(TextKey::Retry),
+                    RetryButton,
+                    ButtonAction::run(handle_retry_button),
+                    SlideIn::nth(0),
+                ),
             );
             spawn_button(
                 parent,
-                MAIN_MENU_BUTTON_TEXT,
+                locale.text(main_menu_button_key),
                 fonts.button_font.clone(),
-                MainMenuButton,
+                (
+                    LocalizedText(main_menu_button_key),
+                    ButtonAction::run(handle_main_menu_button),
+                    CancelButton,
+                    SlideIn::nth(1),
+                ),
             );
         });
 }
@@ -50,23 +101,30 @@ pub(crate) fn despawn_retry_menu(
 }
 
 pub(crate) fn handle_retry_button(
-    mut button_query: Query<&Interaction, (Changed<Interaction>, With<RetryButton>)>,
-    mut next_app_state: ResMut<NextState<AppState>>,
+    session: Option<Res<RaceSession>>,
+    mut transition_events: EventWriter<StartTransition>,
 ) {
-    if let Ok(interaction) = button_query.get_single_mut() {
-        if let Interaction::Pressed = interaction {
-            next_app_state.set(AppState::Playing)
-        }
-    }
+    // Racers regroup in the lobby so the host can start the next race
+    let to = match session {
+        Some(_) => AppState::Lobby,
+        None => AppState::Playing,
+    };
+    transition_events.send(StartTransition {
+        to,
+        style: TransitionStyle::Wipe,
+    });
 }
 
 pub(crate) fn handle_main_menu_button(
-    mut button_query: Query<&Interaction, (Changed<Interaction>, With<MainMenuButton>)>,
-    mut next_app_state: ResMut<NextState<AppState>>,
+    game_mode: Res<GameMode>,
+    mut transition_events: EventWriter<StartTransition>,
 ) {
-    if let Ok(interaction) = button_query.get_single_mut() {
-        if let Interaction::Pressed = interaction {
-            next_app_state.set(AppState::MainMenu)
-        }
-    }
+    let to = match *game_mode {
+        GameMode::Playtest { .. } => AppState::Editor,
+        _ => AppState::MainMenu,
+    };
+    transition_events.send(StartTransition {
+        to,
+        style: TransitionStyle::Wipe,
+    });
 }
@@ -1,6 +1,4 @@
 use super::*;
-use bevy::audio::Volume;
-use std::time::Duration;
 
 #[derive(Resource)]
 pub(crate) struct Score(pub(crate) u32);
@@ -8,9 +6,6 @@ pub(crate) struct Score(pub(crate) u32);
 #[derive(Component)]
 pub(crate) struct ScoreDisplay;
 
-#[derive(Component)]
-pub(crate) struct ScoreTimer(Timer);
-
 pub(crate) fn spawn_score_display(
     mut commands: Commands,
     mut score: ResMut<Score>,
@@ -49,40 +44,15 @@ pub(crate) fn despawn_score_display(
     }
 }
 
-impl ScoreTimer {
-    pub(crate) fn new() -> ScoreTimer {
-        ScoreTimer(Timer::new(
-            Duration::from_secs_f32(SCORE_TIMER_INTERVAL),
-            TimerMode::Repeating,
-        ))
-    }
-}
-
-pub(crate) fn handle_score_timer(
-    mut commands: Commands,
-    mut score: ResMut<Score>,
-    mut timer_query: Query<&mut ScoreTimer>,
+pub(crate) fn update_score_display(
+    score: Res<Score>,
     mut score_display_query: Query<&Children, With<ScoreDisplay>>,
     mut text_query: Query<&mut Text>,
-    time: Res<Time>,
-    audio: Res<AudioAssets>,
 ) {
-    if let Ok(mut delay_timer) = timer_query.get_single_mut() {
-        if delay_timer.0.tick(time.delta()).just_finished() {
-            score.0 += 1;
-            commands.spawn((
-                AudioPlayer::new(audio.score_sound.clone()),
-                PlaybackSettings {
-                    volume: Volume::new(SCORE_SOUND_VOLUME),
-                    ..Default::default()
-                },
-            ));
-            if let Ok(children) = score_display_query.get_single_mut() {
-                for &child in children.iter() {
-                    if let Ok(mut text) = text_query.get_mut(child) {
-                        text.0 = score.0.to_string();
-                    }
-                }
+    if let Ok(children) = score_display_query.get_single_mut() {
+        for &child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.0 = score.0.to_string();
             }
         }
     }
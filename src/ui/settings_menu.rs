@@ -0,0 +1,473 @@
+use super::*;
+
+#[derive(Component)]
+pub(crate) struct SettingsMenu;
+
+/// Switches the Settings menu to its next page
+#[derive(Component)]
+pub(crate) struct SettingsPageButton;
+
+/// Group of settings the Settings menu is showing, since they do not all fit at once
+#[derive(Resource, Component, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum SettingsPage {
+    #[default]
+    Display,
+    Sound,
+    Effects,
+    Language,
+    Accessibility,
+}
+
+impl SettingsPage {
+    fn next(self) -> SettingsPage {
+        match self {
+            SettingsPage::Display => SettingsPage::Sound,
+            SettingsPage::Sound => SettingsPage::Effects,
+            SettingsPage::Effects => SettingsPage::Language,
+            SettingsPage::Language => SettingsPage::Accessibility,
+            SettingsPage::Accessibility => SettingsPage::Display,
+        }
+    }
+
+    fn label(self) -> TextKey {
+        match self {
+            SettingsPage::Display => TextKey::DisplayPage,
+            SettingsPage::Sound => TextKey::SoundPage,
+            SettingsPage::Effects => TextKey::EffectsPage,
+            SettingsPage::Language => TextKey::LanguagePage,
+            SettingsPage::Accessibility => TextKey::AccessPage,
+        }
+    }
+
+    fn row_display(self, current_page: SettingsPage) -> Display {
+        if self == current_page {
+            Display::Flex
+        } else {
+            Display::None
+        }
+    }
+}
+
+/// Steps the setting to its next value when pressed
+#[derive(Component, Clone, Copy)]
+pub(crate) enum SettingButton {
+    Scaling,
+    Portrait,
+    Fullscreen,
+    VSync,
+    FrameCap,
+    Filtering,
+    MasterVolume,
+    MusicVolume,
+    SfxVolume,
+    UiVolume,
+    ScreenEffects,
+    ShakeIntensity,
+    FlashIntensity,
+    HitStopIntensity,
+    Language,
+    HighContrast,
+    ReducedMotion,
+    GameSpeed,
+    ForgivingCollisions,
+}
+
+impl SettingButton {
+    const ALL: [SettingButton; 19] = [
+        SettingButton::Scaling,
+        SettingButton::Portrait,
+        SettingButton::Fullscreen,
+        SettingButton::VSync,
+        SettingButton::FrameCap,
+        SettingButton::Filtering,
+        SettingButton::MasterVolume,
+        SettingButton::MusicVolume,
+        SettingButton::SfxVolume,
+        SettingButton::UiVolume,
+        SettingButton::ScreenEffects,
+        SettingButton::ShakeIntensity,
+        SettingButton::FlashIntensity,
+        SettingButton::HitStopIntensity,
+        SettingButton::Language,
+        SettingButton::HighContrast,
+        SettingButton::ReducedMotion,
+        SettingButton::GameSpeed,
+        SettingButton::ForgivingCollisions,
+    ];
+
+    fn page(self) -> SettingsPage {
+        match self {
+            SettingButton::MasterVolume
+            | SettingButton::MusicVolume
+            | SettingButton::SfxVolume
+            | SettingButton::UiVolume => SettingsPage::Sound,
+            SettingButton::ScreenEffects
+            | SettingButton::ShakeIntensity
+            | SettingButton::FlashIntensity
+            | SettingButton::HitStopIntensity => SettingsPage::Effects,
+            SettingButton::Language => SettingsPage::Language,
+            SettingButton::HighContrast
+            | SettingButton::ReducedMotion
+            | SettingButton::GameSpeed
+            | SettingButton::ForgivingCollisions => SettingsPage::Accessibility,
+            _ => SettingsPage::Display,
+        }
+    }
+
+    /// Browsers decide the frame rate themselves
+    fn is_available(self) -> bool {
+        match self {
+            SettingButton::VSync | SettingButton::FrameCap => !cfg!(target_arch = "wasm32"),
+            _ => true,
+        }
+    }
+
+    fn label(self) -> TextKey {
+        match self {
+            SettingButton::Scaling => TextKey::ScalingSetting,
+            SettingButton::Portrait => TextKey::PortraitSetting,
+            SettingButton::Fullscreen => TextKey::FullscreenSetting,
+            SettingButton::VSync => TextKey::VSyncSetting,
+            SettingButton::FrameCap => TextKey::FrameCapSetting,
+            SettingButton::Filtering => TextKey::FilteringSetting,
+            SettingButton::MasterVolume => TextKey::MasterVolumeSetting,
+            SettingButton::MusicVolume => TextKey::MusicVolumeSetting,
+            SettingButton::SfxVolume => TextKey::SfxVolumeSetting,
+            SettingButton::UiVolume => TextKey::UiVolumeSetting,
+            SettingButton::ScreenEffects => TextKey::ScreenEffectsSetting,
+            SettingButton::ShakeIntensity => TextKey::ShakeSetting,
+            SettingButton::FlashIntensity => TextKey::FlashSetting,
+            SettingButton::HitStopIntensity => TextKey::HitStopSetting,
+            SettingButton::Language => TextKey::LanguageSetting,
+            SettingButton::HighContrast => TextKey::HighContrastSetting,
+            SettingButton::ReducedMotion => TextKey::ReducedMotionSetting,
+            SettingButton::GameSpeed => TextKey::GameSpeedSetting,
+            SettingButton::ForgivingCollisions => TextKey::ForgivingSetting,
+        }
+    }
+
+    fn value_text(
+        self,
+        settings: &Settings,
+        fullscreen_override: &FullscreenOverride,
+        locale: &Locale,
+    ) -> String {
+        match self {
+            SettingButton::Scaling => match settings.scaling {
+                PlayfieldScaling::Fit => locale.text(TextKey::ScalingFit).to_string(),
+                PlayfieldScaling::Integer => locale.text(TextKey::ScalingInteger).to_string(),
+            },
+            SettingButton::Portrait => on_off_text(settings.portrait, locale),
+            SettingButton::Fullscreen => {
+                on_off_text(fullscreen_override.fullscreen(settings), locale)
+            }
+            SettingButton::VSync => on_off_text(settings.vsync, locale),
+            SettingButton::FrameCap => settings
+                .frame_cap
+                .map_or(locale.text(TextKey::SettingOff).to_string(), |frame_cap| {
+                    frame_cap.to_string()
+                }),
+            SettingButton::Filtering => match settings.filtering {
+                TextureFiltering::Sharp => locale.text(TextKey::FilteringSharp).to_string(),
+                TextureFiltering::Smooth => locale.text(TextKey::FilteringSmooth).to_string(),
+            },
+            SettingButton::MasterVolume => percent_text(settings.master_volume),
+            SettingButton::MusicVolume => percent_text(settings.music_volume),
+            SettingButton::SfxVolume => percent_text(settings.sfx_volume),
+            SettingButton::UiVolume => percent_text(settings.ui_volume),
+            SettingButton::ScreenEffects => on_off_text(settings.screen_effects, locale),
+            SettingButton::ShakeIntensity => percent_text(settings.shake_intensity),
+            SettingButton::FlashIntensity => percent_text(settings.flash_intensity),
+            SettingButton::HitStopIntensity => percent_text(settings.hit_stop_intensity),
+            // Each language names itself, so the one in use is always readable
+            SettingButton::Language => locale.name.clone(),
+            SettingButton::HighContrast => on_off_text(settings.high_contrast, locale),
+            SettingButton::ReducedMotion => on_off_text(settings.reduced_motion, locale),
+            SettingButton::GameSpeed => percent_text(settings.game_speed()),
+            SettingButton::ForgivingCollisions => {
+                on_off_text(settings.forgiving_collisions, locale)
+            }
+        }
+    }
+
+    fn next_value(self, settings: &mut Settings, fullscreen_override: &mut FullscreenOverride) {
+        match self {
+            SettingButton::Scaling => {
+                settings.scaling = match settings.scaling {
+                    PlayfieldScaling::Fit => PlayfieldScaling::Integer,
+                    PlayfieldScaling::Integer => PlayfieldScaling::Fit,
+                }
+            }
+            SettingButton::Portrait => settings.portrait = !settings.portrait,
+            // Toggling starts from what is shown and drops any launch override, so the choice is saved
+            SettingButton::Fullscreen => {
+                settings.fullscreen = !fullscreen_override.fullscreen(settings);
+                fullscreen_override.0 = None;
+            }
+            SettingButton::VSync => settings.vsync = !settings.vsync,
+            SettingButton::FrameCap => {
+                let index = FRAME_CAP_CHOICES
+                    .iter()
+                    .position(|choice| *choice == settings.frame_cap)
+                    .map_or(0, |index| index + 1);
+                settings.frame_cap = FRAME_CAP_CHOICES[index % FRAME_CAP_CHOICES.len()];
+            }
+            SettingButton::Filtering => {
+                settings.filtering = match settings.filtering {
+                    TextureFiltering::Sharp => TextureFiltering::Smooth,
+                    TextureFiltering::Smooth => TextureFiltering::Sharp,
+                }
+            }
+            SettingButton::MasterVolume => {
+                settings.master_volume = next_step(settings.master_volume, &BUS_VOLUME_STEPS)
+            }
+            SettingButton::MusicVolume => {
+                settings.music_volume = next_step(settings.music_volume, &BUS_VOLUME_STEPS)
+            }
+            SettingButton::SfxVolume => {
+                settings.sfx_volume = next_step(settings.sfx_volume, &BUS_VOLUME_STEPS)
+            }
+            SettingButton::UiVolume => {
+                settings.ui_volume = next_step(settings.ui_volume, &BUS_VOLUME_STEPS)
+            }
+            SettingButton::ScreenEffects => settings.screen_effects = !settings.screen_effects,
+            SettingButton::ShakeIntensity => {
+                settings.shake_intensity =
+                    next_step(settings.shake_intensity, &EFFECT_INTENSITY_STEPS)
+            }
+            SettingButton::FlashIntensity => {
+                settings.flash_intensity =
+                    next_step(settings.flash_intensity, &EFFECT_INTENSITY_STEPS)
+            }
+            SettingButton::HitStopIntensity => {
+                settings.hit_stop_intensity =
+                    next_step(settings.hit_stop_intensity, &EFFECT_INTENSITY_STEPS)
+            }
+            SettingButton::Language => {
+                let index = LANGUAGES
+                    .iter()
+                    .position(|language| language.key == settings.language)
+                    .map_or(0, |index| index + 1);
+                settings.language = LANGUAGES[index % LANGUAGES.len()].key.to_string();
+            }
+            SettingButton::HighContrast => settings.high_contrast = !settings.high_contrast,
+            SettingButton::ReducedMotion => settings.reduced_motion = !settings.reduced_motion,
+            SettingButton::GameSpeed => {
+                settings.game_speed = next_step(settings.game_speed(), &GAME_SPEED_STEPS)
+            }
+            SettingButton::ForgivingCollisions => {
+                settings.forgiving_collisions = !settings.forgiving_collisions
+            }
+        }
+    }
+}
+
+fn on_off_text(on: bool, locale: &Locale) -> String {
+    if on {
+        locale.text(TextKey::SettingOn).to_string()
+    } else {
+        locale.text(TextKey::SettingOff).to_string()
+    }
+}
+
+fn percent_text(value: f32) -> String {
+    format!("{}%", (value * 100.0).round())
+}
+
+/// Steps up through the choices and wraps from the last back to the first
+fn next_step(value: f32, steps: &[f32]) -> f32 {
+    steps
+        .iter()
+        .copied()
+        .find(|step| *step > value + f32::EPSILON)
+        .unwrap_or(steps[0])
+}
+
+pub(crate) fn spawn_settings_menu(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    locale: Res<Locale>,
+    settings: Res<Settings>,
+    fullscreen_override: Res<FullscreenOverride>,
+    page: Res<SettingsPage>,
+) {
+    commands
+        .spawn((
+            Node {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(SETTINGS_ROW_GAP_PX),
+                padding: UiRect::top(Val::Px(MENU_TOP_PADDING_PX)),
+                ..Default::default()
+            },
+            SettingsMenu,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        justify_self: JustifySelf::Center,
+                        top: Val::Px(TITLE_TOP_MARGIN_PX),
+                        ..Default::default()
+                    },
+                    LocalizedText(TextKey::Settings),
+                ))
+                .with_children(|parent| {
+                    spawn_outlined_text(
+                        parent,
+                        locale.text(TextKey::Settings),
+                        fonts.title_font.clone(),
+                        TITLE_FONT_SIZE_PX,
+                        TITLE_COLOR,
+                        TITLE_OUTLINE_COLOR,
+                        TITLE_OUTLINE_WIDTH_PX,
+                    );
+                });
+            for setting in SettingButton::ALL
+                .into_iter()
+                .filter(|setting| setting.is_available())
+            {
+                parent
+                    .spawn((
+                        Node {
+                            display: setting.page().row_display(*page),
+                            align_items: AlignItems::Center,
+                            column_gap: Val::Px(SETTINGS_ROW_GAP_PX),
+                            ..Default::default()
+                        },
+                        setting.page(),
+                    ))
+                    .with_children(|parent| {
+                        parent
+                            .spawn((
+                                Node {
+                                    width: Val::Px(SETTING_LABEL_WIDTH_PX),
+                                    ..Default::default()
+                                },
+                                LocalizedText(setting.label()),
+                            ))
+                            .with_children(|parent| {
+                                spawn_outlined_text(
+                                    parent,
+                                    locale.text(setting.label()),
+                                    fonts.button_font.clone(),
+                                    SETTING_LABEL_FONT_SIZE_PX,
+                                    TITLE_COLOR,
+                                    TITLE_OUTLINE_COLOR,
+                                    SETTING_LABEL_OUTLINE_WIDTH_PX,
+                                );
+                            });
+                        spawn_sized_button(
+                            parent,
+                            &setting.value_text(&settings, &fullscreen_override, &locale),
+                            fonts.button_font.clone(),
+                            SETTING_BUTTON_WIDTH_PX,
+                            SETTING_BUTTON_HEIGHT_PX,
+                            SETTING_BUTTON_FONT_SIZE_PX,
+                            (
+                                setting,
+                                ButtonAction::run_with_button(handle_setting_button),
+                            ),
+                        );
+                    });
+            }
+            parent
+                .spawn(Node {
+                    display: Display::Flex,
+                    column_gap: Val::Px(SETTINGS_ROW_GAP_PX),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    spawn_sized_button(
+                        parent,
+                        locale.text(TextKey::Back),
+                        fonts.button_font.clone(),
+                        SMALL_BUTTON_WIDTH_PX,
+                        SMALL_BUTTON_HEIGHT_PX,
+                        SMALL_BUTTON_FONT_SIZE_PX,
+                        (
+                            LocalizedText(TextKey::Back),
+                            ButtonAction::open(AppState::MainMenu),
+                            CancelButton,
+                        ),
+                    );
+                    spawn_sized_button(
+                        parent,
+                        locale.text(page.next().label()),
+                        fonts.button_font.clone(),
+                        SMALL_BUTTON_WIDTH_PX,
+                        SMALL_BUTTON_HEIGHT_PX,
+                        SMALL_BUTTON_FONT_SIZE_PX,
+                        (
+                            SettingsPageButton,
+                            ButtonAction::run(handle_settings_page_button),
+                        ),
+                    );
+                });
+        });
+}
+
+pub(crate) fn despawn_settings_menu(
+    mut commands: Commands,
+    settings_menu_query: Query<Entity, With<SettingsMenu>>,
+) {
+    if let Ok(settings_menu) = settings_menu_query.get_single() {
+        commands.entity(settings_menu).despawn_recursive();
+    }
+}
+
+pub(crate) fn update_settings_menu(
+    settings: Res<Settings>,
+    fullscreen_override: Res<FullscreenOverride>,
+    locale: Res<Locale>,
+    setting_button_query: Query<(&SettingButton, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (setting, children) in setting_button_query.iter() {
+        for &child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.0 = setting.value_text(&settings, &fullscreen_override, &locale);
+            }
+        }
+    }
+}
+
+/// Shows the rows of the current page and names the next one on the page button
+pub(crate) fn update_settings_page(
+    page: Res<SettingsPage>,
+    locale: Res<Locale>,
+    mut row_query: Query<(&mut Node, &SettingsPage)>,
+    page_button_query: Query<&Children, With<SettingsPageButton>>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (mut node, row_page) in row_query.iter_mut() {
+        node.display = row_page.row_display(*page);
+    }
+    for children in page_button_query.iter() {
+        for &child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.0 = locale.text(page.next().label()).to_string();
+            }
+        }
+    }
+}
+
+pub(crate) fn handle_settings_page_button(mut page: ResMut<SettingsPage>) {
+    *page = page.next();
+}
+
+pub(crate) fn handle_setting_button(
+    In(button): In<Entity>,
+    button_query: Query<&SettingButton>,
+    mut settings: ResMut<Settings>,
+    mut fullscreen_override: ResMut<FullscreenOverride>,
+) {
+    if let Ok(setting) = button_query.get(button) {
+        setting.next_value(&mut settings, &mut fullscreen_override);
+    }
+}
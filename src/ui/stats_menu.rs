@@ -0,0 +1,344 @@
+use super::*;
+
+#[derive(Component)]
+pub(crate) struct StatsMenu;
+
+/// Writes the run history to the data directory in the given format
+#[derive(Component)]
+pub(crate) struct StatsExportButton(ExportFormat);
+
+pub(crate) fn spawn_stats_menu(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    locale: Res<Locale>,
+    stats: Res<PlayerStats>,
+) {
+    commands
+        .spawn((
+            Node {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(STATS_ROW_GAP_PX),
+                padding: UiRect::top(Val::Px(MENU_TOP_PADDING_PX)),
+                ..Default::default()
+            },
+            StatsMenu,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        justify_self: JustifySelf::Center,
+                        top: Val::Px(TITLE_TOP_MARGIN_PX),
+                        ..Default::default()
+                    },
+                    LocalizedText(TextKey::Stats),
+                ))
+                .with_children(|parent| {
+                    spawn_outlined_text(
+                        parent,
+                        locale.text(TextKey::Stats),
+                        fonts.title_font.clone(),
+                        TITLE_FONT_SIZE_PX,
+                        TITLE_COLOR,
+                        TITLE_OUTLINE_COLOR,
+                        TITLE_OUTLINE_WIDTH_PX,
+                    );
+                });
+            parent
+                .spawn(Node {
+                    display: Display::Flex,
+                    column_gap: Val::Px(STATS_COLUMN_GAP_PX),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    spawn_totals(parent, &fonts, &locale, &stats);
+                    spawn_death_causes(parent, &fonts, &locale, &stats);
+                });
+            spawn_score_chart(parent, &fonts, &locale, &stats);
+            parent
+                .spawn(Node {
+                    display: Display::Flex,
+                    column_gap: Val::Px(STATS_BUTTON_GAP_PX),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    spawn_sized_button(
+                        parent,
+                        locale.text(TextKey::Back),
+                        fonts.button_font.clone(),
+                        SMALL_BUTTON_WIDTH_PX,
+                        SMALL_BUTTON_HEIGHT_PX,
+                        SMALL_BUTTON_FONT_SIZE_PX,
+                        (
+                            LocalizedText(TextKey::Back),
+                            ButtonAction::open(AppState::MainMenu),
+                            CancelButton,
+                        ),
+                    );
+                    // Browsers cannot write files
+                    if !cfg!(target_arch = "wasm32") {
+                        for format in [ExportFormat::Csv, ExportFormat::Json] {
+                            spawn_sized_button(
+                                parent,
+                                &format.extension().to_ascii_uppercase(),
+                                fonts.button_font.clone(),
+                                SMALL_BUTTON_WIDTH_PX,
+                                SMALL_BUTTON_HEIGHT_PX,
+                                SMALL_BUTTON_FONT_SIZE_PX,
+                                (
+                                    StatsExportButton(format),
+                                    ButtonAction::run_with_button(handle_stats_export_button),
+                                ),
+                            );
+                        }
+                    }
+                });
+        });
+}
+
+fn spawn_totals(
+    parent: &mut ChildBuilder,
+    fonts: &FontAssets,
+    locale: &Locale,
+    stats: &PlayerStats,
+) {
+    let totals = [
+        (
+            locale.text(TextKey::StatsRuns),
+            stats.total_runs.to_string(),
+        ),
+        (
+            locale.text(TextKey::StatsFlaps),
+            stats.total_flaps.to_string(),
+        ),
+        (
+            locale.text(TextKey::StatsPipes),
+            stats.total_pipes.to_string(),
+        ),
+        (
+            locale.text(TextKey::StatsPlayTime),
+            format_duration(stats.total_play_time),
+        ),
+        (
+            locale.text(TextKey::StatsAverage),
+            format!("{:.1}", stats.average_score()),
+        ),
+        (
+            locale.text(TextKey::StatsMedian),
+            format!("{:.1}", stats.median_score()),
+        ),
+        (
+            locale.text(TextKey::StatsLongest),
+            format_duration(stats.longest_survival as f64),
+        ),
+    ];
+    parent
+        .spawn(Node {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Column,
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            for (label, value) in totals {
+                parent
+                    .spawn(Node {
+                        display: Display::Flex,
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        spawn_stats_text(parent, fonts, label, STATS_LABEL_WIDTH_PX);
+                        spawn_stats_text(parent, fonts, &value, STATS_VALUE_WIDTH_PX);
+                    });
+            }
+        });
+}
+
+fn spawn_death_causes(
+    parent: &mut ChildBuilder,
+    fonts: &FontAssets,
+    locale: &Locale,
+    stats: &PlayerStats,
+) {
+    let most_deaths = DeathCause::ALL
+        .iter()
+        .map(|cause| stats.deaths(*cause))
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    parent
+        .spawn(Node {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Column,
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            spawn_stats_text(
+                parent,
+                fonts,
+                locale.text(TextKey::StatsDeaths),
+                STATS_LABEL_WIDTH_PX,
+            );
+            for cause in DeathCause::ALL {
+                let deaths = stats.deaths(cause);
+                parent
+                    .spawn(Node {
+                        display: Display::Flex,
+                        align_items: AlignItems::Center,
+                        column_gap: Val::Px(STATS_BAR_GAP_PX),
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        spawn_stats_text(
+                            parent,
+                            fonts,
+                            locale.text(death_cause_key(cause)),
+                            STATS_LABEL_WIDTH_PX,
+                        );
+                        parent.spawn((
+                            Node {
+                                width: Val::Px(
+                                    STATS_DEATH_BAR_WIDTH_PX * deaths as f32 / most_deaths as f32,
+                                ),
+                                height: Val::Px(STATS_DEATH_BAR_HEIGHT_PX),
+                                ..Default::default()
+                            },
+                            BackgroundColor(Color::srgb_from_array(BUTTON_COLOR)),
+                        ));
+                        spawn_stats_text(parent, fonts, &deaths.to_string(), 0.0);
+                    });
+            }
+        });
+}
+
+/// Bars for the most recent scores, scaled so the best of them fills the chart
+fn spawn_score_chart(
+    parent: &mut ChildBuilder,
+    fonts: &FontAssets,
+    locale: &Locale,
+    stats: &PlayerStats,
+) {
+    if stats.recent_scores.is_empty() {
+        spawn_stats_text(parent, fonts, locale.text(TextKey::StatsNoRuns), 0.0);
+        return;
+    }
+    let best = stats
+        .recent_scores
+        .iter()
+        .copied()
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    spawn_stats_text(parent, fonts, locale.text(TextKey::StatsChart), 0.0);
+    parent
+        .spawn((
+            Node {
+                display: Display::Flex,
+                align_items: AlignItems::FlexEnd,
+                column_gap: Val::Px(STATS_CHART_BAR_GAP_PX),
+                width: Val::Px(STATS_CHART_WIDTH_PX),
+                height: Val::Px(STATS_CHART_HEIGHT_PX),
+                padding: UiRect::all(Val::Px(STATS_CHART_BAR_GAP_PX)),
+                ..Default::default()
+            },
+            BackgroundColor(Color::BLACK.with_alpha(STATS_CHART_BACKGROUND_ALPHA)),
+        ))
+        .with_children(|parent| {
+            for score in stats.recent_scores.iter() {
+                let height = STATS_CHART_BAR_MAX_HEIGHT_PX * *score as f32 / best as f32;
+                parent.spawn((
+                    Node {
+                        width: Val::Px(STATS_CHART_BAR_WIDTH_PX),
+                        height: Val::Px(height.max(STATS_CHART_BAR_MIN_HEIGHT_PX)),
+                        ..Default::default()
+                    },
+                    BackgroundColor(Color::srgb_from_array(BUTTON_COLOR)),
+                ));
+            }
+        });
+}
+
+/// A line of stats text, `width` of zero leaves the node sized to the text
+fn spawn_stats_text(parent: &mut ChildBuilder, fonts: &FontAssets, text: &str, width: f32) {
+    parent
+        .spawn(Node {
+            width: if width > 0.0 {
+                Val::Px(width)
+            } else {
+                Val::Auto
+            },
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            spawn_outlined_text(
+                parent,
+                text,
+                fonts.button_font.clone(),
+                STATS_FONT_SIZE_PX,
+                TITLE_COLOR,
+                TITLE_OUTLINE_COLOR,
+                STATS_OUTLINE_WIDTH_PX,
+            );
+        });
+}
+
+fn death_cause_key(cause: DeathCause) -> TextKey {
+    match cause {
+        DeathCause::TopPipe => TextKey::StatsTopPipe,
+        DeathCause::BottomPipe => TextKey::StatsBottomPipe,
+        DeathCause::Ceiling => TextKey::StatsCeiling,
+        DeathCause::Floor => TextKey::StatsFloor,
+    }
+}
+
+/// Formats seconds as `m:ss`, or `h:mm:ss` once an hour has passed
+pub(crate) fn format_duration(seconds: f64) -> String {
+    let seconds = seconds as u64;
+    let (hours, minutes, seconds) = (seconds / 3_600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+pub(crate) fn despawn_stats_menu(
+    mut commands: Commands,
+    stats_menu_query: Query<Entity, With<StatsMenu>>,
+) {
+    if let Ok(stats_menu) = stats_menu_query.get_single() {
+        commands.entity(stats_menu).despawn_recursive();
+    }
+}
+
+pub(crate) fn handle_stats_export_button(
+    In(button): In<Entity>,
+    button_query: Query<&StatsExportButton>,
+    history: Res<RunHistory>,
+    mut toast_events: EventWriter<ShowToast>,
+    locale: Res<Locale>,
+) {
+    let Ok(export_button) = button_query.get(button) else {
+        return;
+    };
+    match export_run_history_file(&history, export_button.0) {
+        Ok(file_name) => {
+            toast_events.send(ShowToast {
+                header: TextKey::ToastExported,
+                message: file_name,
+            });
+        }
+        Err(error) => {
+            warn!("Could not export run history: {error}");
+            toast_events.send(ShowToast {
+                header: TextKey::ToastExportFailed,
+                message: locale.text(TextKey::ToastSeeLog).to_string(),
+            });
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use super::*;
+
+/// A notice that slides in from the right edge, waits and slides back out
+#[derive(Component)]
+pub(crate) struct Toast {
+    elapsed: f32,
+}
+
+/// Asks for a toast with a small `header` line above a larger `message`
+#[derive(Event)]
+pub(crate) struct ShowToast {
+    pub(crate) header: TextKey,
+    pub(crate) message: String,
+}
+
+pub(crate) fn queue_achievement_toasts(
+    mut unlocked_events: EventReader<AchievementUnlocked>,
+    mut toast_events: EventWriter<ShowToast>,
+    locale: Res<Locale>,
+) {
+    for unlocked in unlocked_events.read() {
+        toast_events.send(ShowToast {
+            header: TextKey::ToastAchievement,
+            message: locale.achievement_name(&unlocked.key).to_string(),
+        });
+    }
+}
+
+pub(crate) fn spawn_toasts(
+    mut commands: Commands,
+    mut toast_events: EventReader<ShowToast>,
+    toast_query: Query<(), With<Toast>>,
+    fonts: Res<FontAssets>,
+    locale: Res<Locale>,
+) {
+    // New toasts stack below the ones still on screen
+    let first_slot = toast_query.iter().count();
+    for (slot, toast) in (first_slot..).zip(toast_events.read()) {
+        commands
+            .spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(TOAST_TOP_MARGIN_PX + slot as f32 * TOAST_SPACING_PX),
+                    right: Val::Px(toast_offset(0.0)),
+                    width: Val::Px(TOAST_WIDTH_PX),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(TOAST_PADDING_PX)),
+                    border: UiRect::all(Val::Px(BUTTON_BORDER_PX)),
+                    ..Default::default()
+                },
+                BorderColor(Color::srgb_from_array(BUTTON_BORDER_COLOR)),
+                BorderRadius::all(Val::Px(BUTTON_BORDER_RADIUS_PX)),
+                BackgroundColor(Color::srgb_from_array(BUTTON_COLOR)),
+                GlobalZIndex(TOAST_Z_INDEX),
+                Toast { elapsed: 0.0 },
+            ))
+            .with_children(|parent| {
+                spawn_button_text(
+                    parent,
+                    locale.text(toast.header),
+                    fonts.button_font.clone(),
+                    TOAST_HEADER_FONT_SIZE_PX,
+                );
+                spawn_button_text(
+                    parent,
+                    &toast.message,
+                    fonts.button_font.clone(),
+                    TOAST_NAME_FONT_SIZE_PX,
+                );
+            });
+    }
+}
+
+// Real time keeps toasts moving at the same pace while slow motion is active
+pub(crate) fn animate_toasts(
+    mut commands: Commands,
+    mut toast_query: Query<(Entity, &mut Toast, &mut Node)>,
+    time: Res<Time<Real>>,
+) {
+    for (entity, mut toast, mut node) in toast_query.iter_mut() {
+        toast.elapsed += time.delta_secs();
+        let shown = if toast.elapsed < TOAST_SLIDE_DURATION {
+            toast.elapsed / TOAST_SLIDE_DURATION
+        } else if toast.elapsed < TOAST_SLIDE_DURATION + TOAST_HOLD_DURATION {
+            1.0
+        } else {
+            1.0 - (toast.elapsed - TOAST_SLIDE_DURATION - TOAST_HOLD_DURATION)
+                / TOAST_SLIDE_DURATION
+        };
+        if shown < 0.0 {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        node.right = Val::Px(toast_offset(shown));
+    }
+}
+
+/// Distance from the right edge for a toast that is `shown` of the way on screen, eased out
+fn toast_offset(shown: f32) -> f32 {
+    let eased = 1.0 - (1.0 - shown).powi(2);
+    -TOAST_WIDTH_PX + (TOAST_WIDTH_PX + TOAST_RIGHT_MARGIN_PX) * eased
+}
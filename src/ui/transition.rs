@@ -0,0 +1,185 @@
+use super::*;
+use bevy::ui::FocusPolicy;
+
+/// How the screen is covered while the state changes underneath
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum TransitionStyle {
+    /// Fades to black and back
+    Fade,
+    /// Sweeps a black panel across from left to right
+    Wipe,
+}
+
+/// Asks for a state change behind a transition, which is ignored while another one runs
+#[derive(Event)]
+pub(crate) struct StartTransition {
+    pub(crate) to: AppState,
+    pub(crate) style: TransitionStyle,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum TransitionPhase {
+    #[default]
+    Idle,
+    /// The screen is being covered, after which the state changes
+    Covering,
+    /// The new state is being uncovered
+    Revealing,
+}
+
+/// The transition in progress, if any
+#[derive(Resource, Default)]
+pub(crate) struct ScreenTransition {
+    phase: TransitionPhase,
+    to: Option<AppState>,
+    style: Option<TransitionStyle>,
+    /// How much of the screen is covered, from 0.0 to 1.0
+    coverage: f32,
+}
+
+impl ScreenTransition {
+    pub(crate) fn is_active(&self) -> bool {
+        self.phase != TransitionPhase::Idle
+    }
+
+    pub(crate) fn is_wiping(&self) -> bool {
+        self.is_active() && self.style == Some(TransitionStyle::Wipe)
+    }
+}
+
+/// The panel covering the screen during a transition
+#[derive(Component)]
+pub(crate) struct TransitionOverlay;
+
+/// Slides a menu element in from the left after a delay
+#[derive(Component)]
+pub(crate) struct SlideIn {
+    delay: f32,
+    elapsed: f32,
+}
+
+impl SlideIn {
+    /// The `index`th element of a menu, which starts a little after the one before it
+    pub(crate) fn nth(index: u32) -> SlideIn {
+        SlideIn {
+            delay: index as f32 * SLIDE_IN_STAGGER,
+            elapsed: 0.0,
+        }
+    }
+}
+
+pub(crate) fn spawn_transition_overlay(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            display: Display::None,
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..Default::default()
+        },
+        BackgroundColor(Color::srgb_from_array(TRANSITION_COLOR)),
+        // Stops buttons underneath from being pressed while they are covered
+        FocusPolicy::Block,
+        GlobalZIndex(TRANSITION_Z_INDEX),
+        TransitionOverlay,
+    ));
+}
+
+pub(crate) fn start_transitions(
+    mut start_events: EventReader<StartTransition>,
+    mut transition: ResMut<ScreenTransition>,
+    mut play_sound_events: EventWriter<PlaySound>,
+) {
+    for StartTransition { to, style } in start_events.read() {
+        if transition.is_active() {
+            continue;
+        }
+        *transition = ScreenTransition {
+            phase: TransitionPhase::Covering,
+            to: Some(to.clone()),
+            style: Some(*style),
+            coverage: 0.0,
+        };
+        // The swoosh follows the panel across the screen
+        if *style == TransitionStyle::Wipe {
+            play_sound_events.send(PlaySound(SoundEffect::Swoosh));
+        }
+    }
+}
+
+// Real time so hit-stop and slow motion do not hold up the menus
+pub(crate) fn advance_transition(
+    mut transition: ResMut<ScreenTransition>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+    time: Res<Time<Real>>,
+) {
+    match transition.phase {
+        TransitionPhase::Idle => (),
+        TransitionPhase::Covering => {
+            transition.coverage += time.delta_secs() / TRANSITION_COVER_DURATION;
+            if transition.coverage >= 1.0 {
+                transition.coverage = 1.0;
+                transition.phase = TransitionPhase::Revealing;
+                if let Some(to) = transition.to.take() {
+                    next_app_state.set(to);
+                }
+            }
+        }
+        TransitionPhase::Revealing => {
+            transition.coverage -= time.delta_secs() / TRANSITION_REVEAL_DURATION;
+            if transition.coverage <= 0.0 {
+                *transition = ScreenTransition::default();
+            }
+        }
+    }
+}
+
+pub(crate) fn update_transition_overlay(
+    transition: Res<ScreenTransition>,
+    mut overlay_query: Query<(&mut Node, &mut BackgroundColor), With<TransitionOverlay>>,
+) {
+    let Ok((mut node, mut background_color)) = overlay_query.get_single_mut() else {
+        return;
+    };
+    let Some(style) = transition.style.filter(|_| transition.is_active()) else {
+        node.display = Display::None;
+        return;
+    };
+    node.display = Display::Flex;
+    let color = Color::srgb_from_array(TRANSITION_COLOR);
+    match style {
+        TransitionStyle::Fade => {
+            node.left = Val::Percent(0.0);
+            node.width = Val::Percent(100.0);
+            background_color.0 = color.with_alpha(transition.coverage);
+        }
+        TransitionStyle::Wipe => {
+            // The panel grows in from the left, then leaves through the right
+            let left = match transition.phase {
+                TransitionPhase::Revealing => 1.0 - transition.coverage,
+                _ => 0.0,
+            };
+            node.left = Val::Percent(left * 100.0);
+            node.width = Val::Percent(transition.coverage * 100.0);
+            background_color.0 = color;
+        }
+    }
+}
+
+pub(crate) fn slide_in_menu_elements(
+    mut commands: Commands,
+    mut slide_query: Query<(Entity, &mut SlideIn, &mut Node)>,
+    time: Res<Time<Real>>,
+) {
+    for (entity, mut slide, mut node) in slide_query.iter_mut() {
+        slide.elapsed += time.delta_secs();
+        let progress = ((slide.elapsed - slide.delay) / SLIDE_IN_DURATION).clamp(0.0, 1.0);
+        // Eases out so elements settle gently into place
+        let remaining = (1.0 - progress).powi(3);
+        node.left = Val::Px(-SLIDE_IN_DISTANCE_PX * remaining);
+        if progress >= 1.0 {
+            node.left = Val::Auto;
+            commands.entity(entity).remove::<SlideIn>();
+        }
+    }
+}